@@ -20,7 +20,10 @@ impl CliError {
 
 impl Display for CliError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{self:?}")
+        match self {
+            CliError::FrispError(e) => write!(f, "{e}"),
+            CliError::GenericError(e) => write!(f, "{e}"),
+        }
     }
 }
 
@@ -42,6 +45,10 @@ struct CliArgs {
     #[arg(short, long)]
     include: Vec<String>,
 
+    /// maximum number of frames on the evaluation stack
+    #[arg(long)]
+    max_depth: Option<usize>,
+
     /// subcommand to run
     #[command(subcommand)]
     command: Option<Commands>,
@@ -52,9 +59,10 @@ struct CliArgs {
 
 }
 
-#[derive(Debug, Subcommand)]
+#[derive(Debug, Default, Subcommand)]
 enum Commands {
     /// run the interactive repl
+    #[default]
     Repl,
 
     /// run a script from a file
@@ -70,12 +78,6 @@ enum Commands {
     },
 }
 
-impl Default for Commands {
-    fn default() -> Self {
-        Commands::Repl
-    }
-}
-
 fn run_repl(env: &mut Environment) -> Result<(), CliError> {
     loop {
         let mut input = String::new();
@@ -102,6 +104,10 @@ fn main() -> Result<(), CliError> {
 
     let mut env = Environment::with_default_content();
 
+    if let Some(max_depth) = args.max_depth {
+        env.set_max_depth(max_depth);
+    }
+
     for include_glob in &args.include {
         for include in glob::glob(include_glob).map_err(CliError::generic_error)? {
             libfrisp::eval_file_with_env(include.map_err(CliError::generic_error)?, &mut env)?;
@@ -109,7 +115,7 @@ fn main() -> Result<(), CliError> {
     }
 
     env.insert_var("cli-args", ConstVal::from(args.args.iter().map(Value::string).collect::<Value>()));
-    env.insert_var("sys-env", ConstVal::from(std::env::vars().map(|(a, b)| Value::List(vec![Value::string(a), Value::string(b)])).collect::<Value>()));
+    env.insert_var("sys-env", ConstVal::from(Value::map(std::env::vars().map(|(a, b)| (Value::string(a), Value::string(b))))?));
 
     match &args.command.unwrap_or_default() {
         Commands::Repl => run_repl(&mut env),
@@ -1,20 +1,26 @@
 
 use std::{fmt::Debug, rc::Rc};
 
-use crate::{env::{Env, Environment}, token::{Token, TokenStream}, value::{ConstVal, Lambda, Value}, Error};
+use crate::{env::Environment, eval::Evaluator, number, token::{Token, TokenStream}, value::Value, Error};
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum AstNode {
-    List(Vec<AstNode>),
+    List(Rc<[AstNode]>),
+    /// A dotted list `(a b . c)`, whose last pair has `c` as its rest instead of the empty list.
+    Dotted(Rc<[AstNode]>, Rc<AstNode>),
     Symbol(String),
     Value(Value),
+    /// A map literal `{k v ...}` with its keys and values in alternation.
+    Map(Rc<[AstNode]>),
+    /// A vector literal `[a b ...]`.
+    Vector(Rc<[AstNode]>),
 }
 
 impl AstNode {
 
     pub fn try_to_list(self) -> Result<Vec<AstNode>, AstNode> {
         match self {
-            AstNode::List(list) => Ok(list),
+            AstNode::List(list) => Ok(list.to_vec()),
             o => Err(o),
         }
     }
@@ -33,30 +39,75 @@ impl AstNode {
         }
     }
 
+    /// Creates the dotted list of `items` ending with `tail`. If `tail` is a list,
+    /// the result is the proper list of `items` followed by the elements of `tail`.
+    pub fn dotted(mut items: Vec<AstNode>, tail: AstNode) -> AstNode {
+        if tail.as_form("unquote").is_some() {
+            return AstNode::Dotted(items.into(), Rc::new(tail));
+        }
+
+        match tail {
+            AstNode::List(list) => {
+                items.extend(list.iter().cloned());
+                AstNode::List(items.into())
+            },
+            AstNode::Dotted(list, tail) => {
+                items.extend(list.iter().cloned());
+                AstNode::Dotted(items.into(), tail)
+            },
+            tail => AstNode::Dotted(items.into(), Rc::new(tail)),
+        }
+    }
+
     pub fn parse_raw_symbol(raw_symbol: &str) -> AstNode {
-        if let Ok(int_value) = raw_symbol.parse::<isize>() {
-            return AstNode::Value(Value::Integer(int_value));
+        match raw_symbol {
+            "#t" | "#true" => return AstNode::Value(Value::Bool(true)),
+            "#f" | "#false" => return AstNode::Value(Value::Bool(false)),
+            _ => {},
+        }
+
+        if let Some(keyword) = raw_symbol.strip_prefix(':').filter(|k| !k.is_empty()) {
+            return AstNode::Value(Value::Keyword(keyword.to_string()));
         }
 
-        if let Ok(float_value) = raw_symbol.parse::<f64>() {
-            return AstNode::Value(Value::Float(float_value));
+        if let Some(number) = number::parse(raw_symbol) {
+            return AstNode::Value(number);
         }
+
         AstNode::Symbol(raw_symbol.to_string())
     }
 
 }
 
+/// Turns data back into code, the inverse of quoting.
+impl From<Value> for AstNode {
+    fn from(value: Value) -> Self {
+        match value {
+            Value::List(list) => AstNode::List(list.into_iter().map(AstNode::from).collect()),
+            Value::Pair(pair) => {
+                let (items, rest) = Value::pair_parts(&pair);
+                AstNode::Dotted(items.into_iter().cloned().map(AstNode::from).collect(), Rc::new(AstNode::from(rest.clone())))
+            },
+            Value::Map(map) => AstNode::Map(map.iter().flat_map(|(k, v)| [AstNode::from(k.value().clone()), AstNode::from(v.clone())]).collect()),
+            Value::Vector(vector) => AstNode::Vector(vector.iter().cloned().map(AstNode::from).collect()),
+            Value::SymbolRef(symbol) => AstNode::Symbol(symbol),
+            Value::Unit => AstNode::default(),
+            value => AstNode::Value(value),
+        }
+    }
+}
+
 impl Default for AstNode {
     fn default() -> Self {
-        AstNode::List(Vec::new())
+        AstNode::List(Rc::new([]))
     }
 }
 
-pub struct AstNodeStream<I> {
+pub struct AstNodeStream<I: Iterator> {
     token_stream: TokenStream<I>,
 }
 
-impl<I> AstNodeStream<I> {
+impl<I: Iterator> AstNodeStream<I> {
     pub fn new(token_stream: TokenStream<I>) -> AstNodeStream<I> {
         AstNodeStream { token_stream }
     }
@@ -74,219 +125,265 @@ where I: Iterator<Item = char> {
     type Item = Result<AstNode, Error>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        let mut lists = Vec::new();
-        let mut current_list: Option<Vec<AstNode>> = None;
+        let mut pending = Vec::new();
 
-        while let Some(t) = self.token_stream.next() {
-            match t {
+        for t in self.token_stream.by_ref() {
+            let node = match t {
                 Ok(Token::ListStart) => {
-                    if let Some(l) = current_list.take() {
-                        lists.push(l);
-                    }
-                    current_list = Some(Vec::new());
+                    pending.push(Pending::List(Brackets::Round, Vec::new()));
+                    continue;
                 },
-                Ok(Token::ListEnd) => {
-                    let list = current_list.take().ok_or(Error::ParserError("list end without current list".to_string()));
-                    if let Err(e) = list {
-                        return Some(Err(e))
-                    }
-                    let list = list.unwrap();
-                    let parent_list = lists.pop();
-                    match parent_list {
-                        Some(mut pl) => {
-                            pl.push(AstNode::List(list));
-                            current_list = Some(pl);
-                        },
-                        None => return Some(Ok(AstNode::List(list)))
-                    }
+                Ok(Token::MapStart) => {
+                    pending.push(Pending::List(Brackets::Curly, Vec::new()));
+                    continue;
                 },
-                Ok(Token::Symbol(s)) => {
-                    let value = AstNode::parse_raw_symbol(&s);
-                    if let Some(l) = current_list.as_mut() {
-                        l.push(value);
-                    } else {
-                        return Some(Ok(value));
-                    }
+                Ok(Token::VectorStart) => {
+                    pending.push(Pending::List(Brackets::Square, Vec::new()));
+                    continue;
+                },
+                Ok(Token::ListEnd) => match Pending::close(&mut pending, Brackets::Round) {
+                    Ok(node) => node,
+                    Err(e) => return Some(Err(e)),
+                },
+                Ok(Token::MapEnd) => match Pending::close(&mut pending, Brackets::Curly) {
+                    Ok(node) => node,
+                    Err(e) => return Some(Err(e)),
+                },
+                Ok(Token::VectorEnd) => match Pending::close(&mut pending, Brackets::Square) {
+                    Ok(node) => node,
+                    Err(e) => return Some(Err(e)),
+                },
+                Ok(Token::Quote) => {
+                    pending.push(Pending::Prefix("quote"));
+                    continue;
+                },
+                Ok(Token::Quasiquote) => {
+                    pending.push(Pending::Prefix("quasiquote"));
+                    continue;
                 },
-                Ok(Token::String(s)) => {
-                    let value = AstNode::Value(Value::String(s));
-                    if let Some(l) = current_list.as_mut() {
-                        l.push(value);
-                    } else {
-                        return Some(Ok(value));
+                Ok(Token::Unquote) => {
+                    pending.push(Pending::Prefix("unquote"));
+                    continue;
+                },
+                Ok(Token::UnquoteSplicing) => {
+                    pending.push(Pending::Prefix("unquote-splicing"));
+                    continue;
+                },
+                Ok(Token::Symbol(s)) if s == "." => {
+                    match pending.last() {
+                        Some(Pending::List(Brackets::Round, list)) if !list.is_empty() => pending.push(Pending::Dot(None)),
+                        _ => return Some(Err(Error::ParserError("dot outside of a list".to_string()))),
                     }
+                    continue;
                 },
+                Ok(Token::Symbol(s)) => AstNode::parse_raw_symbol(&s),
+                Ok(Token::String(s)) => AstNode::Value(Value::String(s)),
+                Ok(Token::Char(c)) => AstNode::Value(Value::Char(c)),
                 Err(e) => {
                     return Some(Err(e));
                 }
+            };
+
+            match Pending::complete(&mut pending, node) {
+                Ok(Some(node)) => return Some(Ok(node)),
+                Ok(None) => {},
+                Err(e) => return Some(Err(e)),
             }
         }
 
-        if current_list.is_none() {
-            return None;
+        if pending.is_empty() {
+            None
         } else {
-            return Some(Err(Error::ParserError("reached end of stream without end of list".to_string())));
+            Some(Err(Error::ParserError("reached end of stream without end of list".to_string())))
         }
 
     }
 
 }
 
-impl AstNode {
+/// Brackets around a list of datums.
+///
+/// Map literals `{k v ...}` are read as [`AstNode::Map`] and vector literals `[a b ...]`
+/// as [`AstNode::Vector`].
+#[derive(Clone, Copy, PartialEq)]
+enum Brackets {
+    Round,
+    Curly,
+    Square,
+}
+
+impl Brackets {
 
-    fn quote(&self) -> Value {
+    fn name(self) -> &'static str {
         match self {
-            AstNode::List(list) => list.iter().map(|n| n.quote()).collect(),
-            AstNode::Symbol(symbol) => Value::SymbolRef(symbol.to_owned()),
-            AstNode::Value(value) => value.clone(),
+            Brackets::Round => "list",
+            Brackets::Curly => "map",
+            Brackets::Square => "vector",
         }
-    } 
-
-    fn eval_list(env: &mut Environment, symbol: &str, l: &Vec<AstNode>) -> Result<Value, Error> {
-        match symbol {
-            "if" => {
-                let test = l.get(1).ok_or(Error::EvalError(format!("missing test")))?;
-                let conseq = l.get(2).ok_or(Error::EvalError(format!("missing conseq")))?;
-                let alt = l.get(3).ok_or(Error::EvalError(format!("missing alt")))?;
-
-
-                if test.eval(env)? == Value::Integer(1) {
-                    return conseq.eval(env);
-                } else {
-                    return alt.eval(env);
-                }
-            },
-            "define" => {
-                let symbol = l.get(1).ok_or(Error::EvalError(format!("no symbol for define")))?;
-                let val = l.get(2).ok_or(Error::EvalError(format!("no value for define")))?;
-
-                if let AstNode::Symbol(sym) = symbol {
-                    let value = val.eval(env)?;
-                    #[cfg(feature = "log")]
-                    println!("defined {sym} to be {value:?}");
-                    env.insert_var(sym, ConstVal::from(value));
-                }
-
-
-                return Ok(Value::Unit);
-            },
-            "lambda" => {
-                let args = l.get(1).ok_or(Error::EvalError(format!("no args for lambda")))?;
-                let body: Vec<_> = l[2..].iter().map(|n| n.to_owned()).collect();
+    }
 
-                let args = args.to_owned().try_to_list().map_err(|n| Error::EvalError(format!("not a list: {n:?}")))?;
+}
 
-                let args: Result<Vec<String>, Error> = args.into_iter()
-                    .map(|v| v.try_to_symbol()
-                        .map_err(|n| Error::EvalError(format!("not a symbol: {n:?}")))
-                    ).collect();
+/// Partially read datum of an `AstNodeStream`.
+enum Pending {
+    List(Brackets, Vec<AstNode>),
+    /// A reader shorthand like `'` that wraps the next datum into a form named by the prefix.
+    Prefix(&'static str),
+    /// The dot of a dotted list, with the datum after it once it is read.
+    Dot(Option<AstNode>),
+}
 
-                let args = args?;
+impl Pending {
 
-                return Ok(Value::Lambda(Rc::new(Lambda::new(args, body))));
-            },
-            "progn" => {
-                let mut last_value = None;
+    /// Adds a completely read `node` to the innermost pending datum,
+    /// or returns it if there is nothing pending anymore.
+    fn complete(pending: &mut Vec<Pending>, mut node: AstNode) -> Result<Option<AstNode>, Error> {
+        loop {
+            match pending.last_mut() {
+                Some(Pending::List(_, list)) => {
+                    list.push(node);
+                    return Ok(None);
+                },
+                Some(Pending::Dot(tail @ None)) => {
+                    *tail = Some(node);
+                    return Ok(None);
+                },
+                Some(Pending::Dot(Some(_))) => return Err(Error::ParserError("more than one datum after dot".to_string())),
+                Some(Pending::Prefix(prefix)) => {
+                    node = AstNode::List(Rc::new([AstNode::Symbol(prefix.to_string()), node]));
+                    pending.pop();
+                },
+                None => return Ok(Some(node)),
+            }
+        }
+    }
 
-                for stmt in &l[1..] {
-                    last_value = Some(stmt.eval(env)?)
+    /// Completes the innermost pending list, which has to be in `brackets`.
+    fn close(pending: &mut Vec<Pending>, brackets: Brackets) -> Result<AstNode, Error> {
+        let name = brackets.name();
+        match pending.pop() {
+            Some(Pending::List(Brackets::Curly, list)) if brackets == Brackets::Curly => {
+                if !list.len().is_multiple_of(2) {
+                    return Err(Error::ParserError("map literal with a key without value".to_string()));
                 }
-                
-                last_value.ok_or(Error::VarEvalError(format!("no value")))
+                Ok(AstNode::Map(list.into()))
             },
-            "quote" => {
-                let v: Value = l[1..].iter().map(|v| v.quote()).collect();
-                Ok(v.unwrap_single_value_list())
+            Some(Pending::List(Brackets::Square, list)) if brackets == Brackets::Square => Ok(AstNode::Vector(list.into())),
+            Some(Pending::List(b, list)) if b == brackets => Ok(AstNode::List(list.into())),
+            Some(Pending::Dot(Some(tail))) if brackets == Brackets::Round => match pending.pop() {
+                Some(Pending::List(_, list)) => Ok(AstNode::dotted(list, tail)),
+                _ => Err(Error::ParserError("dot outside of a list".to_string())),
             },
-            "try" => {
-                let mut last_value = None;
-
-                for ast in &l[1..] {
-                    match ast.eval(env) {
-                        Ok(v) => last_value = Some(v),
-                        Err(e) => {
-                            last_value = Some(Value::Error(e.to_string()));
-                            break;
-                        },
-                    }
-                }
-
-                last_value.ok_or(Error::VarEvalError(format!("no value")))
-            },
-            #[cfg(feature = "eval")]
-            "eval" => {
-                let script = l.get(1).ok_or(Error::EvalError(format!("no args for eval")))?;
-                let script_val = script.eval(env).map_err(|v| Error::EvalError(format!("{v:?} is not a value")))?;
-                let script_str = script_val.as_str().ok_or(Error::EvalError(format!("{script_val:?} is not a string")))?;
+            Some(Pending::Dot(_)) => Err(Error::ParserError(format!("{name} end after dot without a datum"))),
+            Some(Pending::List(b, _)) => Err(Error::ParserError(format!("{name} end inside of a {}", b.name()))),
+            Some(Pending::Prefix(prefix)) => Err(Error::ParserError(format!("{name} end after {prefix} without a datum"))),
+            None => Err(Error::ParserError(format!("{name} end without current {name}"))),
+        }
+    }
 
-                let res = crate::run_with_env(script_str, env)?;
+}
 
-                #[cfg(feature = "log")]
-                println!("evaluated {script_str:?} to {res:?}");
-                Ok(res)
-            },
+impl AstNode {
 
-            #[cfg(feature = "include")]
-            "include" => {
-                let path = l.get(1).ok_or(Error::EvalError(format!("no args for include")))?;
-                let path_val = path.to_owned().try_to_value().map_err(|v| Error::EvalError(format!("{v:?} is not a value")))?;
-                let path_str = path_val.as_str().ok_or(Error::EvalError(format!("{path_val:?} is not a string")))?;
+    pub fn quote(&self) -> Value {
+        match self {
+            AstNode::List(list) => list.iter().map(|n| n.quote()).collect(),
+            AstNode::Dotted(list, tail) => list.iter().rev().fold(tail.quote(), |rest, n| Value::cons(n.quote(), rest)),
+            AstNode::Symbol(symbol) => Value::SymbolRef(symbol.to_owned()),
+            AstNode::Value(value) => value.clone(),
+            AstNode::Map(items) => Value::map_of_entries(items.iter().map(|n| n.quote()).collect())
+                .unwrap_or_else(|e| Value::Error(e.to_string())),
+            AstNode::Vector(items) => Value::Vector(Rc::new(items.iter().map(|n| n.quote()).collect())),
+        }
+    } 
 
-                crate::eval_file_with_env(path_str, env)
+    /// Returns the argument if this node is the form `(name arg)`.
+    fn as_form(&self, name: &str) -> Option<&AstNode> {
+        match self {
+            AstNode::List(list) => match &list[..] {
+                [AstNode::Symbol(s), arg] if s == name => Some(arg),
+                _ => None,
             },
+            _ => None,
+        }
+    }
 
-            s => {
-                let mut args: Vec<Value> = Vec::new();
+    /// Collects the expressions of all `unquote` and `unquote-splicing` forms in a quasiquote
+    /// template that belong to the quasiquote at `depth` 1.
+    pub fn quasiquote_holes(&self, depth: usize, holes: &mut Vec<AstNode>) {
+        if let Some(arg) = self.as_form("unquote").or_else(|| self.as_form("unquote-splicing")) {
+            if depth == 1 {
+                holes.push(arg.clone());
+            } else {
+                arg.quasiquote_holes(depth - 1, holes);
+            }
+        } else if let Some(arg) = self.as_form("quasiquote") {
+            arg.quasiquote_holes(depth + 1, holes);
+        } else if let AstNode::List(list) | AstNode::Map(list) | AstNode::Vector(list) = self {
+            for node in list.iter() {
+                node.quasiquote_holes(depth, holes);
+            }
+        } else if let AstNode::Dotted(list, tail) = self {
+            for node in list.iter() {
+                node.quasiquote_holes(depth, holes);
+            }
+            tail.quasiquote_holes(depth, holes);
+        }
+    }
 
-                for v in &l[1..] {
-                    args.push(v.eval(env)?);
-                }
+    /// Quotes a quasiquote template, filling its holes with the evaluated `values`
+    /// in the order they were returned by [`AstNode::quasiquote_holes`].
+    pub fn quasiquote(&self, depth: usize, values: &mut impl Iterator<Item = Value>) -> Result<Value, Error> {
+        if let Some(arg) = self.as_form("unquote") {
+            if depth == 1 {
+                return values.next().ok_or(Error::EvalError("missing value for unquote".to_string()));
+            }
+            return Ok(Value::List(vec![Value::SymbolRef("unquote".to_string()), arg.quasiquote(depth - 1, values)?].into()));
+        }
 
-                let var = env.get_var(s).ok_or(Error::EvalError(format!("proc not found: {s}")))?;
-                let value = var.eval(&env, args);
-                #[cfg(feature = "log")]
-                println!("evaluated {s} to {value:?}");
-                value
+        if let Some(arg) = self.as_form("unquote-splicing") {
+            if depth == 1 {
+                return Err(Error::EvalError("unquote-splicing outside of a list".to_string()));
             }
+            return Ok(Value::List(vec![Value::SymbolRef("unquote-splicing".to_string()), arg.quasiquote(depth - 1, values)?].into()));
+        }
 
+        if let Some(arg) = self.as_form("quasiquote") {
+            return Ok(Value::List(vec![Value::SymbolRef("quasiquote".to_string()), arg.quasiquote(depth + 1, values)?].into()));
         }
-    }
 
-    pub fn eval(&self, env: &mut Environment) -> Result<Value, Error> {
         match self {
-            AstNode::List(l) => {
-                #[cfg(feature = "log")]
-                println!("evaluating {:?}", l.get(0));
-
-                match l.get(0) {
-                    Some(AstNode::Symbol(s)) => {
-                        Self::eval_list(env, s, l)
-                    },
-                    None => {
-                        Ok(Value::Unit)
-                    },
-                    _ => {
-                        return Err(Error::EvalError(format!("invalid at this point in time: {l:?}")))
-                    }
-                }
-            },
-            AstNode::Symbol(s) => {
-                let var = env.get_var(&s).ok_or(Error::EvalError(format!("symbol not found: {s:?}")))?;
-                let res = Ok(var.val().unwrap_or_else(|| Value::SymbolRef(s.clone())));
-                #[cfg(feature = "log")]
-                println!("Symboling {s:?} to {res:?}");
-                res
-            },
-            AstNode::Value(v) => {
-                #[cfg(feature = "log")]
-                println!("Valuing {v:?}");
-                Ok(v.clone())
+            AstNode::List(list) => Ok(Value::List(Self::quasiquote_items(list, depth, values)?.into())),
+            AstNode::Dotted(list, tail) => {
+                let items = Self::quasiquote_items(list, depth, values)?;
+                let tail = tail.quasiquote(depth, values)?;
+                Ok(items.into_iter().rev().fold(tail, |rest, item| Value::cons(item, rest)))
             },
+            AstNode::Map(items) => Value::map_of_entries(Self::quasiquote_items(items, depth, values)?),
+            AstNode::Vector(items) => Ok(Value::Vector(Rc::new(Self::quasiquote_items(items, depth, values)?))),
+            node => Ok(node.quote()),
         }
     }
 
-}
+    fn quasiquote_items(list: &[AstNode], depth: usize, values: &mut impl Iterator<Item = Value>) -> Result<Vec<Value>, Error> {
+        let mut result = Vec::new();
+        for node in list.iter() {
+            if depth == 1 && node.as_form("unquote-splicing").is_some() {
+                let spliced = values.next().ok_or(Error::EvalError("missing value for unquote-splicing".to_string()))?;
+                let spliced = spliced.to_list().ok_or(Error::EvalError("unquote-splicing of a value that is not a list".to_string()))?;
+                result.extend(spliced);
+            } else {
+                result.push(node.quasiquote(depth, values)?);
+            }
+        }
+        Ok(result)
+    }
 
+    pub fn eval(&self, env: &mut Environment) -> Result<Value, Error> {
+        Evaluator::new(env.max_depth()).eval(self, env)
+    }
+
+}
 
 #[cfg(test)]
 mod tests {
@@ -0,0 +1,619 @@
+use std::{cmp::Ordering, fmt::Display, ops::{Add, BitAnd, BitOr, BitXor, Mul, Neg, Not, Sub}};
+
+/// Arbitrary precision integer.
+///
+/// Stored as a sign and a magnitude of base 2^32 limbs, least significant first. The magnitude
+/// never has leading zero limbs and zero is never negative, so equal numbers are equal structurally.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct BigInt {
+    negative: bool,
+    limbs: Vec<u32>,
+}
+
+const LIMB_BITS: u32 = 32;
+
+/// Most bits a computed result may have. Operations that would exceed it report an overflow
+/// instead of trying to allocate the result.
+pub const MAX_BITS: usize = 1 << 24;
+
+/// Largest power of ten in a limb, used to convert from and to decimal.
+const DECIMAL_CHUNK: u32 = 1_000_000_000;
+const DECIMAL_CHUNK_DIGITS: usize = 9;
+
+impl BigInt {
+
+    fn from_parts(negative: bool, mut limbs: Vec<u32>) -> BigInt {
+        while limbs.last() == Some(&0) {
+            limbs.pop();
+        }
+        BigInt { negative: negative && !limbs.is_empty(), limbs }
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.limbs.is_empty()
+    }
+
+    pub fn is_negative(&self) -> bool {
+        self.negative
+    }
+
+    /// Returns the value as `isize` if it is in its range.
+    pub fn to_isize(&self) -> Option<isize> {
+        if self.limbs.len() > 3 {
+            return None;
+        }
+        let magnitude = self.limbs.iter().rev().fold(0i128, |acc, &limb| (acc << LIMB_BITS) | limb as i128);
+        isize::try_from(if self.negative { -magnitude } else { magnitude }).ok()
+    }
+
+    /// Returns the nearest `f64`, which is infinite if the value is out of its range.
+    pub fn to_f64(&self) -> f64 {
+        let magnitude = self.limbs.iter().rev().fold(0f64, |acc, &limb| acc * (1u64 << LIMB_BITS) as f64 + limb as f64);
+        if self.negative { -magnitude } else { magnitude }
+    }
+
+    /// Parses an optionally signed sequence of decimal digits.
+    pub fn parse(s: &str) -> Option<BigInt> {
+        let (negative, digits) = match s.strip_prefix('-') {
+            Some(digits) => (true, digits),
+            None => (false, s.strip_prefix('+').unwrap_or(s)),
+        };
+
+        if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+            return None;
+        }
+
+        let mut limbs = Vec::new();
+        let first_chunk = match digits.len() % DECIMAL_CHUNK_DIGITS {
+            0 => DECIMAL_CHUNK_DIGITS,
+            n => n,
+        };
+        let (first, mut rest) = digits.split_at(first_chunk);
+        mul_add_small(&mut limbs, 1, first.parse().ok()?);
+        while !rest.is_empty() {
+            let (chunk, tail) = rest.split_at(DECIMAL_CHUNK_DIGITS);
+            mul_add_small(&mut limbs, DECIMAL_CHUNK, chunk.parse().ok()?);
+            rest = tail;
+        }
+
+        Some(BigInt::from_parts(negative, limbs))
+    }
+
+    /// Number of one bits in the magnitude.
+    pub fn count_ones(&self) -> usize {
+        self.limbs.iter().map(|limb| limb.count_ones() as usize).sum()
+    }
+
+    /// Returns `len` limbs of the two's complement representation, which must be long enough
+    /// to hold the sign bit.
+    fn twos_complement(&self, len: usize) -> Vec<u32> {
+        if !self.negative {
+            let mut limbs = self.limbs.clone();
+            limbs.resize(len, 0);
+            return limbs;
+        }
+
+        // -x is the complement of x - 1.
+        let mut limbs: Vec<u32> = sub_magnitude(&self.limbs, &[1]).into_iter().map(|limb| !limb).collect();
+        limbs.resize(len, u32::MAX);
+        limbs
+    }
+
+    fn from_twos_complement(limbs: Vec<u32>) -> BigInt {
+        if limbs.last().is_none_or(|&top| top >> (LIMB_BITS - 1) == 0) {
+            return BigInt::from_parts(false, limbs);
+        }
+        let complement: Vec<u32> = limbs.into_iter().map(|limb| !limb).collect();
+        BigInt::from_parts(true, add_magnitude(&complement, &[1]))
+    }
+
+    /// Combines the two's complement representations of both numbers limb by limb.
+    fn bitwise(&self, other: &BigInt, op: fn(u32, u32) -> u32) -> BigInt {
+        let len = self.limbs.len().max(other.limbs.len()) + 1;
+        let limbs = self.twos_complement(len).into_iter()
+            .zip(other.twos_complement(len))
+            .map(|(a, b)| op(a, b))
+            .collect();
+        BigInt::from_twos_complement(limbs)
+    }
+
+    /// Number of bits in the magnitude, which is zero for zero.
+    pub fn bits(&self) -> usize {
+        self.limbs.last().map_or(0, |top| self.limbs.len() * LIMB_BITS as usize - top.leading_zeros() as usize)
+    }
+
+    pub fn abs(&self) -> BigInt {
+        BigInt { negative: false, limbs: self.limbs.clone() }
+    }
+
+    /// Greatest common divisor, which is never negative and only zero if both are zero.
+    pub fn gcd(&self, other: &BigInt) -> BigInt {
+        let (mut a, mut b) = (self.abs(), other.abs());
+        while let Some((_, remainder)) = a.div_rem(&b) {
+            a = b;
+            b = remainder;
+        }
+        a
+    }
+
+    /// Parses an unsigned sequence of digits in `radix`, which must be between 2 and 36.
+    pub fn parse_radix(digits: &str, radix: u32) -> Option<BigInt> {
+        if digits.is_empty() {
+            return None;
+        }
+
+        let mut limbs = Vec::new();
+        for c in digits.chars() {
+            mul_add_small(&mut limbs, radix, c.to_digit(radix)?);
+        }
+
+        Some(BigInt::from_parts(false, limbs))
+    }
+
+    /// Multiplies by 2 to the power of `bits`.
+    pub fn shl(&self, bits: usize) -> BigInt {
+        let mut limbs = vec![0; bits / LIMB_BITS as usize];
+        limbs.extend(shift_left(&self.limbs, (bits % LIMB_BITS as usize) as u32));
+        BigInt::from_parts(self.negative, limbs)
+    }
+
+    /// Divides by 2 to the power of `bits`, rounding towards negative infinity like an arithmetic
+    /// shift of the two's complement representation.
+    pub fn shr(&self, bits: usize) -> BigInt {
+        let skipped = (bits / LIMB_BITS as usize).min(self.limbs.len());
+        let shift = (bits % LIMB_BITS as usize) as u32;
+        let shifted = BigInt::from_parts(self.negative, shift_right(&self.limbs[skipped..], shift));
+
+        let inexact = self.limbs[..skipped].iter().any(|&limb| limb != 0)
+            || self.limbs.get(skipped).is_some_and(|&limb| limb & ((1 << shift) - 1) != 0);
+        if self.negative && inexact { &shifted - &BigInt::from(1) } else { shifted }
+    }
+
+    /// Divides truncating towards zero, so that the remainder has the sign of `self`.
+    /// Returns `None` if `divisor` is zero.
+    pub fn div_rem(&self, divisor: &BigInt) -> Option<(BigInt, BigInt)> {
+        if divisor.is_zero() {
+            return None;
+        }
+        let (quotient, remainder) = div_rem_magnitude(&self.limbs, &divisor.limbs);
+        Some((
+            BigInt::from_parts(self.negative != divisor.negative, quotient),
+            BigInt::from_parts(self.negative, remainder),
+        ))
+    }
+
+}
+
+impl From<isize> for BigInt {
+    fn from(value: isize) -> Self {
+        let mut magnitude = value.unsigned_abs() as u128;
+        let mut limbs = Vec::new();
+        while magnitude > 0 {
+            limbs.push(magnitude as u32);
+            magnitude >>= LIMB_BITS;
+        }
+        BigInt::from_parts(value < 0, limbs)
+    }
+}
+
+impl Display for BigInt {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.is_zero() {
+            return write!(f, "0");
+        }
+
+        let mut chunks = Vec::new();
+        let mut limbs = self.limbs.clone();
+        while !limbs.is_empty() {
+            let (quotient, remainder) = div_rem_small(&limbs, DECIMAL_CHUNK);
+            chunks.push(remainder);
+            limbs = quotient;
+        }
+
+        if self.negative {
+            write!(f, "-")?;
+        }
+        let mut chunks = chunks.iter().rev();
+        if let Some(first) = chunks.next() {
+            write!(f, "{first}")?;
+        }
+        for chunk in chunks {
+            write!(f, "{chunk:0width$}", width = DECIMAL_CHUNK_DIGITS)?;
+        }
+        Ok(())
+    }
+}
+
+impl PartialOrd for BigInt {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for BigInt {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self.negative, other.negative) {
+            (false, true) => Ordering::Greater,
+            (true, false) => Ordering::Less,
+            (false, false) => cmp_magnitude(&self.limbs, &other.limbs),
+            (true, true) => cmp_magnitude(&other.limbs, &self.limbs),
+        }
+    }
+}
+
+impl Neg for &BigInt {
+    type Output = BigInt;
+
+    fn neg(self) -> Self::Output {
+        BigInt::from_parts(!self.negative, self.limbs.clone())
+    }
+}
+
+/// Bitwise operations work on the infinite two's complement representation,
+/// like they do on the fixed size one of `isize`.
+impl BitAnd for &BigInt {
+    type Output = BigInt;
+
+    fn bitand(self, rhs: Self) -> Self::Output {
+        self.bitwise(rhs, |a, b| a & b)
+    }
+}
+
+impl BitOr for &BigInt {
+    type Output = BigInt;
+
+    fn bitor(self, rhs: Self) -> Self::Output {
+        self.bitwise(rhs, |a, b| a | b)
+    }
+}
+
+impl BitXor for &BigInt {
+    type Output = BigInt;
+
+    fn bitxor(self, rhs: Self) -> Self::Output {
+        self.bitwise(rhs, |a, b| a ^ b)
+    }
+}
+
+impl Not for &BigInt {
+    type Output = BigInt;
+
+    fn not(self) -> Self::Output {
+        &-self - &BigInt::from(1)
+    }
+}
+
+impl Add for &BigInt {
+    type Output = BigInt;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        if self.negative == rhs.negative {
+            return BigInt::from_parts(self.negative, add_magnitude(&self.limbs, &rhs.limbs));
+        }
+
+        match cmp_magnitude(&self.limbs, &rhs.limbs) {
+            Ordering::Less => BigInt::from_parts(rhs.negative, sub_magnitude(&rhs.limbs, &self.limbs)),
+            _ => BigInt::from_parts(self.negative, sub_magnitude(&self.limbs, &rhs.limbs)),
+        }
+    }
+}
+
+impl Sub for &BigInt {
+    type Output = BigInt;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        self + &-rhs
+    }
+}
+
+impl Mul for &BigInt {
+    type Output = BigInt;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        BigInt::from_parts(self.negative != rhs.negative, mul_magnitude(&self.limbs, &rhs.limbs))
+    }
+}
+
+fn cmp_magnitude(a: &[u32], b: &[u32]) -> Ordering {
+    a.len().cmp(&b.len()).then_with(|| a.iter().rev().cmp(b.iter().rev()))
+}
+
+fn add_magnitude(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let (long, short) = if a.len() >= b.len() { (a, b) } else { (b, a) };
+    let mut result = Vec::with_capacity(long.len() + 1);
+    let mut carry = 0u64;
+    for (i, &limb) in long.iter().enumerate() {
+        let sum = limb as u64 + short.get(i).copied().unwrap_or(0) as u64 + carry;
+        result.push(sum as u32);
+        carry = sum >> LIMB_BITS;
+    }
+    result.push(carry as u32);
+    result
+}
+
+/// Subtracts `b` from `a`, which must not be smaller than `b`.
+fn sub_magnitude(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let mut result = Vec::with_capacity(a.len());
+    let mut borrow = 0i64;
+    for (i, &limb) in a.iter().enumerate() {
+        let diff = limb as i64 - b.get(i).copied().unwrap_or(0) as i64 - borrow;
+        result.push(diff as u32);
+        borrow = (diff < 0) as i64;
+    }
+    result
+}
+
+fn mul_magnitude(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let mut result = vec![0u32; a.len() + b.len()];
+    for (i, &x) in a.iter().enumerate() {
+        let mut carry = 0u64;
+        for (j, &y) in b.iter().enumerate() {
+            let product = x as u64 * y as u64 + result[i + j] as u64 + carry;
+            result[i + j] = product as u32;
+            carry = product >> LIMB_BITS;
+        }
+        result[i + b.len()] = carry as u32;
+    }
+    result
+}
+
+/// Sets `limbs` to `limbs * mul + add`.
+fn mul_add_small(limbs: &mut Vec<u32>, mul: u32, add: u32) {
+    let mut carry = add as u64;
+    for limb in limbs.iter_mut() {
+        let value = *limb as u64 * mul as u64 + carry;
+        *limb = value as u32;
+        carry = value >> LIMB_BITS;
+    }
+    if carry > 0 {
+        limbs.push(carry as u32);
+    }
+}
+
+fn div_rem_small(limbs: &[u32], divisor: u32) -> (Vec<u32>, u32) {
+    let mut quotient = vec![0u32; limbs.len()];
+    let mut remainder = 0u64;
+    for (i, &limb) in limbs.iter().enumerate().rev() {
+        let value = (remainder << LIMB_BITS) | limb as u64;
+        quotient[i] = (value / divisor as u64) as u32;
+        remainder = value % divisor as u64;
+    }
+    while quotient.last() == Some(&0) {
+        quotient.pop();
+    }
+    (quotient, remainder as u32)
+}
+
+fn shift_left(limbs: &[u32], shift: u32) -> Vec<u32> {
+    let mut result = Vec::with_capacity(limbs.len() + 1);
+    let mut carry = 0u32;
+    for &limb in limbs {
+        result.push((limb << shift) | carry);
+        carry = if shift == 0 { 0 } else { limb >> (LIMB_BITS - shift) };
+    }
+    result.push(carry);
+    result
+}
+
+fn shift_right(limbs: &[u32], shift: u32) -> Vec<u32> {
+    let mut result = vec![0u32; limbs.len()];
+    for i in 0..limbs.len() {
+        let high = if shift == 0 { 0 } else { limbs.get(i + 1).map_or(0, |&l| l << (LIMB_BITS - shift)) };
+        result[i] = (limbs[i] >> shift) | high;
+    }
+    result
+}
+
+/// Long division of magnitudes following Knuth's algorithm D. `divisor` must not be zero.
+fn div_rem_magnitude(dividend: &[u32], divisor: &[u32]) -> (Vec<u32>, Vec<u32>) {
+    if cmp_magnitude(dividend, divisor) == Ordering::Less {
+        return (Vec::new(), dividend.to_vec());
+    }
+
+    if let [divisor] = divisor {
+        let (quotient, remainder) = div_rem_small(dividend, *divisor);
+        return (quotient, vec![remainder]);
+    }
+
+    // Normalize so that the most significant limb of the divisor has its highest bit set,
+    // which makes the estimated quotient limbs at most two too large.
+    let shift = divisor[divisor.len() - 1].leading_zeros();
+    let v = shift_left(divisor, shift);
+    let v = &v[..divisor.len()];
+    let mut u = shift_left(dividend, shift);
+
+    let n = v.len();
+    let base = 1u64 << LIMB_BITS;
+    let mut quotient = vec![0u32; dividend.len() - n + 1];
+
+    for j in (0..quotient.len()).rev() {
+        let numerator = ((u[j + n] as u64) << LIMB_BITS) | u[j + n - 1] as u64;
+        let mut q = numerator / v[n - 1] as u64;
+        let mut r = numerator % v[n - 1] as u64;
+        while q >= base || q * v[n - 2] as u64 > ((r << LIMB_BITS) | u[j + n - 2] as u64) {
+            q -= 1;
+            r += v[n - 1] as u64;
+            if r >= base {
+                break;
+            }
+        }
+
+        let mut borrow = 0i64;
+        let mut carry = 0u64;
+        for i in 0..n {
+            let product = q * v[i] as u64 + carry;
+            carry = product >> LIMB_BITS;
+            let diff = u[i + j] as i64 - borrow - (product as u32) as i64;
+            u[i + j] = diff as u32;
+            borrow = (diff < 0) as i64;
+        }
+        let diff = u[j + n] as i64 - borrow - carry as i64;
+        u[j + n] = diff as u32;
+
+        if diff < 0 {
+            q -= 1;
+            let mut carry = 0u64;
+            for i in 0..n {
+                let sum = u[i + j] as u64 + v[i] as u64 + carry;
+                u[i + j] = sum as u32;
+                carry = sum >> LIMB_BITS;
+            }
+            u[j + n] = u[j + n].wrapping_add(carry as u32);
+        }
+
+        quotient[j] = q as u32;
+    }
+
+    let mut remainder = shift_right(&u[..n], shift);
+    while remainder.last() == Some(&0) {
+        remainder.pop();
+    }
+    while quotient.last() == Some(&0) {
+        quotient.pop();
+    }
+    (quotient, remainder)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn big(s: &str) -> BigInt {
+        BigInt::parse(s).unwrap()
+    }
+
+    #[test]
+    fn parse_and_display() {
+        for s in ["0", "1", "-1", "4294967296", "-18446744073709551616", "123456789012345678901234567890"] {
+            assert_eq!(s, big(s).to_string());
+        }
+        assert_eq!("0", big("-0").to_string());
+        assert_eq!("12", big("+0012").to_string());
+        assert_eq!(None, BigInt::parse("12a"));
+        assert_eq!(None, BigInt::parse("-"));
+    }
+
+    #[test]
+    fn gcd() {
+        assert_eq!(big("6"), big("-12").gcd(&big("18")));
+        assert_eq!(big("5"), big("0").gcd(&big("-5")));
+        assert_eq!(big("0"), big("0").gcd(&big("0")));
+        assert_eq!(big("4294967296"), big("18446744073709551616").gcd(&big("12884901888")));
+    }
+
+    #[test]
+    fn parse_radix() {
+        assert_eq!(big("255"), BigInt::parse_radix("ff", 16).unwrap());
+        assert_eq!(big("18446744073709551616"), BigInt::parse_radix("10000000000000000", 16).unwrap());
+        assert_eq!(big("5"), BigInt::parse_radix("101", 2).unwrap());
+        assert_eq!(None, BigInt::parse_radix("102", 2));
+        assert_eq!(None, BigInt::parse_radix("", 8));
+    }
+
+    #[test]
+    fn shl() {
+        assert_eq!(big("18446744073709551616"), big("1").shl(64));
+        assert_eq!(big("-393216"), big("-3").shl(17));
+        assert_eq!(big("5"), big("5").shl(0));
+        assert_eq!(big("0"), big("0").shl(100));
+    }
+
+    #[test]
+    fn shr() {
+        assert_eq!(big("1"), big("18446744073709551616").shr(64));
+        assert_eq!(big("-18446744073709551616"), big("-36893488147419103232").shr(1));
+        assert_eq!(big("-2"), big("-18446744073709551617").shr(64));
+        assert_eq!(big("-1"), big("-18446744073709551616").shr(64));
+        assert_eq!(big("-1"), big("-3").shr(100));
+        assert_eq!(big("0"), big("3").shr(100));
+        assert_eq!(big("-2"), big("-3").shr(1));
+        assert_eq!(big("5"), big("5").shr(0));
+        for (v, bits) in [(-(1i128 << 100) - 7, 3), (-(1 << 95), 95), (-(1 << 95), 33), ((1 << 90) + 12345, 31)] {
+            assert_eq!(big(&(v >> bits).to_string()), big(&v.to_string()).shr(bits));
+        }
+    }
+
+    #[test]
+    fn bitwise() {
+        let values = [0i128, 1, -1, 12, -13, (1 << 64) + 5, -(1 << 64), -(1 << 95) - 3, (1 << 100) - 1, -(1 << 32)];
+        for a in values {
+            for b in values {
+                assert_eq!(big(&(a & b).to_string()), &big(&a.to_string()) & &big(&b.to_string()), "{a} & {b}");
+                assert_eq!(big(&(a | b).to_string()), &big(&a.to_string()) | &big(&b.to_string()), "{a} | {b}");
+                assert_eq!(big(&(a ^ b).to_string()), &big(&a.to_string()) ^ &big(&b.to_string()), "{a} ^ {b}");
+            }
+            assert_eq!(big(&(!a).to_string()), !&big(&a.to_string()), "!{a}");
+        }
+        assert_eq!(2, big("18446744073709551617").count_ones());
+    }
+
+    #[test]
+    fn bits() {
+        assert_eq!(0, big("0").bits());
+        assert_eq!(1, big("-1").bits());
+        assert_eq!(32, big("4294967295").bits());
+        assert_eq!(65, big("-18446744073709551616").bits());
+    }
+
+    #[test]
+    fn isize_round_trip() {
+        for v in [0, 1, -1, isize::MAX, isize::MIN, 1 << 40] {
+            assert_eq!(Some(v), BigInt::from(v).to_isize());
+        }
+        assert_eq!(None, (&BigInt::from(isize::MAX) + &BigInt::from(1)).to_isize());
+        assert_eq!(None, (&BigInt::from(isize::MIN) - &BigInt::from(1)).to_isize());
+    }
+
+    #[test]
+    fn arithmetic_matches_i128() {
+        let values: [i128; 8] = [0, 1, -7, 4294967295, -4294967296, 1 << 62, -(1 << 63) + 5, 98765432123456789];
+        for &a in &values {
+            for &b in &values {
+                let (x, y) = (BigInt::from(a as isize), BigInt::from(b as isize));
+                assert_eq!((a + b).to_string(), (&x + &y).to_string());
+                assert_eq!((a - b).to_string(), (&x - &y).to_string());
+                assert_eq!((a * b).to_string(), (&x * &y).to_string());
+                assert_eq!(a.cmp(&b), x.cmp(&y));
+                if b != 0 {
+                    let (q, r) = x.div_rem(&y).unwrap();
+                    assert_eq!(((a / b).to_string(), (a % b).to_string()), (q.to_string(), r.to_string()));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn random_division_matches_i128() {
+        let mut seed = 0x2545F4914F6CDD1Du64;
+        let mut next = || {
+            seed ^= seed << 13;
+            seed ^= seed >> 7;
+            seed ^= seed << 17;
+            seed
+        };
+
+        for _ in 0..2000 {
+            let a = ((next() as i128) << 40) ^ next() as i128;
+            let b = (next() as i128) >> (next() % 64);
+            if b == 0 {
+                continue;
+            }
+            let (x, y) = (big(&a.to_string()), big(&b.to_string()));
+            let (q, r) = x.div_rem(&y).unwrap();
+            assert_eq!(((a / b).to_string(), (a % b).to_string()), (q.to_string(), r.to_string()), "{a} / {b}");
+            assert_eq!((a + b).to_string(), (&x + &y).to_string());
+            assert_eq!((a - b).to_string(), (&x - &y).to_string());
+        }
+    }
+
+    #[test]
+    fn long_division() {
+        let a = big("123456789012345678901234567890123456789012345678901234567890");
+        let b = big("-98765432109876543210987654321");
+        let (q, r) = a.div_rem(&b).unwrap();
+        assert_eq!(a, &(&q * &b) + &r);
+        assert!(cmp_magnitude(&r.limbs, &b.limbs) == Ordering::Less);
+        assert!(!r.is_negative());
+        assert_eq!(None, a.div_rem(&BigInt::default()));
+    }
+}
@@ -1,12 +1,19 @@
-use crate::{value::{Variable, Value}, Error};
+use crate::{bigint::{BigInt, MAX_BITS}, ratio::Ratio, value::{Operands, Variable, Value}, Arity, Error};
 
 use super::Environment;
 
+/// Folds the arguments from the left, returning the only argument as is and unit if there are none.
+fn fold(args: Vec<Value>, op: impl Fn(Value, Value) -> Result<Value, Error>) -> Result<Value, Error> {
+    let mut args = args.into_iter();
+    let first = args.next().unwrap_or_default();
+    args.try_fold(first, op)
+}
+
 pub struct Add;
 
 impl Variable for Add {
     fn eval(&self, _env: &Environment, args: Vec<Value>) -> Result<Value, Error> {
-        args.into_iter().reduce(|accu, v| accu + v).unwrap_or_default().unwrap_err()
+        fold(args, |accu, v| accu + v)
     }
 }
 
@@ -14,7 +21,7 @@ pub struct Sub;
 
 impl Variable for Sub {
     fn eval(&self, _env: &Environment, args: Vec<Value>) -> Result<Value, Error> {
-        args.into_iter().reduce(|accu, v| accu - v).unwrap_or_default().unwrap_err()
+        fold(args, |accu, v| accu - v)
     }
 }
 
@@ -22,7 +29,7 @@ pub struct Mul;
 
 impl Variable for Mul {
     fn eval(&self, _env: &Environment, args: Vec<Value>) -> Result<Value, Error> {
-        args.into_iter().reduce(|accu, v| accu * v).unwrap_or_default().unwrap_err()
+        fold(args, |accu, v| accu * v)
     }
 }
 
@@ -30,7 +37,7 @@ pub struct Div;
 
 impl Variable for Div {
     fn eval(&self, _env: &Environment, args: Vec<Value>) -> Result<Value, Error> {
-        args.into_iter().reduce(|accu, v| accu / v).unwrap_or_default().unwrap_err()
+        fold(args, |accu, v| accu / v)
     }
 }
 
@@ -39,56 +46,369 @@ pub struct Mod;
 impl Variable for Mod {
     fn eval(&self, _env: &Environment, args: Vec<Value>) -> Result<Value, Error> {
         if args.len() != 2 {
-            return Err(Error::VarEvalArgNumError { expected: 2, actual: args.len() });
+            return Err(Error::VarEvalArgNumError { expected: Arity::Exactly(2), actual: args.len() });
         }
 
-        match (&args[0],&args[1]) {
-            (Value::Integer(v1), Value::Integer(v2)) => Ok(Value::Integer(v1 % v2)),
-            (Value::Integer(v1), Value::Float(v2)) => Ok(Value::Float(*v1 as f64 % v2)),
-            (Value::Float(v1), Value::Integer(v2)) => Ok(Value::Float(v1 % *v2 as f64)),
-            (Value::Float(v1), Value::Float(v2)) => Ok(Value::Float(v1 % v2)),
-            (v1, v2) => Err(Error::VarEvalError(format!("cannot mod {v1:?} and {v2:?}"))),
+        match Operands::of(&args[0], &args[1]) {
+            Some(Operands::Int(_, 0)) => Err(Error::DivisionByZero),
+            Some(Operands::Int(v1, v2)) => Ok(Value::Integer(v1.wrapping_rem(v2))),
+            Some(Operands::Big(v1, v2)) => v1.div_rem(&v2)
+                .map(|(_, remainder)| Value::bigint(remainder))
+                .ok_or(Error::DivisionByZero),
+            Some(Operands::Ratio(v1, v2)) => v1.checked_div(&v2)
+                .map(|quotient| Value::ratio(&v1 - &(&v2 * &Ratio::from(quotient.trunc()))))
+                .ok_or(Error::DivisionByZero),
+            Some(Operands::Float(v1, v2)) => Ok(Value::Float(v1 % v2)),
+            None => Err(Error::VarEvalError(format!("cannot mod {:?} and {:?}", args[0], args[1]))),
         }
     }
 }
 
+/// `equal?`, structural equality, so `1` and `1.0` differ unlike with `=`.
 pub struct Eq;
 
 impl Variable for Eq {
     fn eval(&self, _env: &Environment, args: Vec<Value>) -> Result<Value, Error> {
         if args.len() != 2 {
-            return Err(Error::VarEvalArgNumError { expected: 2, actual: args.len() });
+            return Err(Error::VarEvalArgNumError { expected: Arity::Exactly(2), actual: args.len() });
         }
         #[cfg(feature = "log")]
         println!("is {:?} == {:?}?", &args[0], &args[1]);
-        Ok(Value::bool(&args[0] == &args[1]))
+        Ok(Value::bool(args[0] == args[1]))
+    }
+}
+
+pub struct Numerator;
+
+impl Variable for Numerator {
+    fn eval(&self, _env: &Environment, args: Vec<Value>) -> Result<Value, Error> {
+        if args.len() != 1 {
+            return Err(Error::VarEvalArgNumError { expected: Arity::Exactly(1), actual: args.len() });
+        }
+
+        match &args[0] {
+            Value::Ratio(v) => Ok(Value::bigint(v.numer().clone())),
+            v@(Value::Integer(_) | Value::BigInt(_)) => Ok(v.clone()),
+            v => Err(Error::VarEvalError(format!("not an exact number: {v:?}"))),
+        }
+    }
+}
+
+pub struct Denominator;
+
+impl Variable for Denominator {
+    fn eval(&self, _env: &Environment, args: Vec<Value>) -> Result<Value, Error> {
+        if args.len() != 1 {
+            return Err(Error::VarEvalArgNumError { expected: Arity::Exactly(1), actual: args.len() });
+        }
+
+        match &args[0] {
+            Value::Ratio(v) => Ok(Value::bigint(v.denom().clone())),
+            Value::Integer(_) | Value::BigInt(_) => Ok(Value::Integer(1)),
+            v => Err(Error::VarEvalError(format!("not an exact number: {v:?}"))),
+        }
+    }
+}
+
+/// Converts a number to the nearest float.
+pub struct ExactToInexact;
+
+impl Variable for ExactToInexact {
+    fn eval(&self, _env: &Environment, args: Vec<Value>) -> Result<Value, Error> {
+        if args.len() != 1 {
+            return Err(Error::VarEvalArgNumError { expected: Arity::Exactly(1), actual: args.len() });
+        }
+
+        args[0].to_f64().map(Value::Float).ok_or_else(|| Error::VarEvalError(format!("not a number: {:?}", args[0])))
+    }
+}
+
+/// Returns an integer as `isize`, for operations that work on fixed size integers only.
+fn fixnum(value: &Value) -> Result<isize, Error> {
+    match value {
+        Value::Integer(v) => Ok(*v),
+        Value::BigInt(v) => Err(Error::Overflow(format!("{v} does not fit into a fixed size integer"))),
+        v => Err(Error::VarEvalError(format!("not an integer: {v:?}"))),
+    }
+}
+
+fn fold_fixnums(args: Vec<Value>, op: fn(isize, isize) -> isize) -> Result<Value, Error> {
+    fold(args, |accu, v| Ok(Value::Integer(op(fixnum(&accu)?, fixnum(&v)?))))
+}
+
+/// `+%`, addition that wraps around at the bounds of a fixed size integer instead of promoting to a big integer.
+pub struct WrappingAdd;
+
+impl Variable for WrappingAdd {
+    fn eval(&self, _env: &Environment, args: Vec<Value>) -> Result<Value, Error> {
+        fold_fixnums(args, isize::wrapping_add)
     }
 }
 
-pub struct Lt;
+pub struct WrappingSub;
 
-impl Variable for Lt {
+impl Variable for WrappingSub {
+    fn eval(&self, _env: &Environment, args: Vec<Value>) -> Result<Value, Error> {
+        fold_fixnums(args, isize::wrapping_sub)
+    }
+}
+
+pub struct WrappingMul;
+
+impl Variable for WrappingMul {
+    fn eval(&self, _env: &Environment, args: Vec<Value>) -> Result<Value, Error> {
+        fold_fixnums(args, isize::wrapping_mul)
+    }
+}
+
+/// `+|`, addition that clamps to the bounds of a fixed size integer instead of promoting to a big integer.
+pub struct SaturatingAdd;
+
+impl Variable for SaturatingAdd {
+    fn eval(&self, _env: &Environment, args: Vec<Value>) -> Result<Value, Error> {
+        fold_fixnums(args, isize::saturating_add)
+    }
+}
+
+pub struct SaturatingSub;
+
+impl Variable for SaturatingSub {
+    fn eval(&self, _env: &Environment, args: Vec<Value>) -> Result<Value, Error> {
+        fold_fixnums(args, isize::saturating_sub)
+    }
+}
+
+pub struct SaturatingMul;
+
+impl Variable for SaturatingMul {
+    fn eval(&self, _env: &Environment, args: Vec<Value>) -> Result<Value, Error> {
+        fold_fixnums(args, isize::saturating_mul)
+    }
+}
+
+fn integer(value: &Value) -> Result<BigInt, Error> {
+    value.to_bigint().ok_or_else(|| Error::VarEvalError(format!("not an integer: {value:?}")))
+}
+
+/// Divides two integers, with `fixnum` for arguments that are both fixed size integers and `big` otherwise.
+fn divide_integers(args: Vec<Value>, fixnum: fn(isize, isize) -> Value, big: fn(&BigInt, &BigInt) -> BigInt) -> Result<Value, Error> {
+    if args.len() != 2 {
+        return Err(Error::VarEvalArgNumError { expected: Arity::Exactly(2), actual: args.len() });
+    }
+
+    match Operands::of(&args[0], &args[1]) {
+        Some(Operands::Int(_, 0)) => Err(Error::DivisionByZero),
+        Some(Operands::Int(v1, v2)) => Ok(fixnum(v1, v2)),
+        Some(Operands::Big(_, v2)) if v2.is_zero() => Err(Error::DivisionByZero),
+        Some(Operands::Big(v1, v2)) => Ok(Value::bigint(big(&v1, &v2))),
+        _ => Err(Error::VarEvalError(format!("cannot divide {:?} by {:?} as integers", args[0], args[1]))),
+    }
+}
+
+/// `(quotient a b)`, integer division rounding towards zero, so `(quotient -7 2)` is `-3`.
+pub struct Quotient;
+
+impl Variable for Quotient {
+    fn eval(&self, _env: &Environment, args: Vec<Value>) -> Result<Value, Error> {
+        divide_integers(
+            args,
+            |v1, v2| v1.checked_div(v2).map_or_else(|| Value::bigint(-&BigInt::from(v1)), Value::Integer),
+            |v1, v2| v1.div_rem(v2).map(|(quotient, _)| quotient).unwrap_or_default(),
+        )
+    }
+}
+
+/// `(remainder a b)`, the remainder of `quotient`, which has the sign of `a`, so `(remainder -7 2)` is `-1`.
+pub struct Remainder;
+
+impl Variable for Remainder {
+    fn eval(&self, _env: &Environment, args: Vec<Value>) -> Result<Value, Error> {
+        divide_integers(
+            args,
+            |v1, v2| Value::Integer(v1.wrapping_rem(v2)),
+            |v1, v2| v1.div_rem(v2).map(|(_, remainder)| remainder).unwrap_or_default(),
+        )
+    }
+}
+
+/// `(modulo a b)`, the remainder of division rounding towards negative infinity, which has the sign of `b`,
+/// so `(modulo -7 2)` is `1`.
+pub struct Modulo;
+
+impl Variable for Modulo {
+    fn eval(&self, _env: &Environment, args: Vec<Value>) -> Result<Value, Error> {
+        divide_integers(
+            args,
+            |v1, v2| {
+                let remainder = v1.wrapping_rem(v2);
+                Value::Integer(if remainder != 0 && (remainder < 0) != (v2 < 0) { remainder + v2 } else { remainder })
+            },
+            |v1, v2| {
+                let remainder = v1.div_rem(v2).map(|(_, remainder)| remainder).unwrap_or_default();
+                if !remainder.is_zero() && remainder.is_negative() != v2.is_negative() { &remainder + v2 } else { remainder }
+            },
+        )
+    }
+}
+
+/// Greatest common divisor of any number of integers, which is never negative and `0` for no arguments.
+pub struct Gcd;
+
+impl Variable for Gcd {
+    fn eval(&self, _env: &Environment, args: Vec<Value>) -> Result<Value, Error> {
+        args.iter()
+            .try_fold(BigInt::default(), |gcd, v| Ok(gcd.gcd(&integer(v)?)))
+            .map(Value::bigint)
+    }
+}
+
+/// Least common multiple of any number of integers, which is never negative and `1` for no arguments.
+pub struct Lcm;
+
+impl Variable for Lcm {
+    fn eval(&self, _env: &Environment, args: Vec<Value>) -> Result<Value, Error> {
+        args.iter()
+            .try_fold(BigInt::from(1), |lcm, v| {
+                let v = integer(v)?;
+                Ok(match (&lcm * &v).abs().div_rem(&lcm.gcd(&v)) {
+                    Some((lcm, _)) => lcm,
+                    None => BigInt::default(),
+                })
+            })
+            .map(Value::bigint)
+    }
+}
+
+/// Folds integers with `fixnum` while both are fixed size integers and with `big` otherwise.
+/// Big integers act as if their two's complement representation had infinitely many sign bits.
+fn fold_bits(args: Vec<Value>, fixnum: fn(isize, isize) -> isize, big: fn(&BigInt, &BigInt) -> BigInt) -> Result<Value, Error> {
+    fold(args, |accu, v| match (&accu, &v) {
+        (Value::Integer(v1), Value::Integer(v2)) => Ok(Value::Integer(fixnum(*v1, *v2))),
+        (v1, v2) => Ok(Value::bigint(big(&integer(v1)?, &integer(v2)?))),
+    })
+}
+
+pub struct BitAnd;
+
+impl Variable for BitAnd {
+    fn eval(&self, _env: &Environment, args: Vec<Value>) -> Result<Value, Error> {
+        fold_bits(args, |v1, v2| v1 & v2, |v1, v2| v1 & v2)
+    }
+}
+
+pub struct BitOr;
+
+impl Variable for BitOr {
+    fn eval(&self, _env: &Environment, args: Vec<Value>) -> Result<Value, Error> {
+        fold_bits(args, |v1, v2| v1 | v2, |v1, v2| v1 | v2)
+    }
+}
+
+pub struct BitXor;
+
+impl Variable for BitXor {
+    fn eval(&self, _env: &Environment, args: Vec<Value>) -> Result<Value, Error> {
+        fold_bits(args, |v1, v2| v1 ^ v2, |v1, v2| v1 ^ v2)
+    }
+}
+
+/// Complement of the two's complement representation, so `(bit-not x)` is `(- -1 x)`.
+pub struct BitNot;
+
+impl Variable for BitNot {
+    fn eval(&self, _env: &Environment, args: Vec<Value>) -> Result<Value, Error> {
+        if args.len() != 1 {
+            return Err(Error::VarEvalArgNumError { expected: Arity::Exactly(1), actual: args.len() });
+        }
+
+        match &args[0] {
+            Value::Integer(v) => Ok(Value::Integer(!v)),
+            v => Ok(Value::bigint(!&integer(v)?)),
+        }
+    }
+}
+
+/// Number of one bits in the two's complement representation of a fixed size integer,
+/// so negative numbers count their sign bits too. Negative big integers have infinitely
+/// many one bits and report an overflow.
+pub struct Popcount;
+
+impl Variable for Popcount {
+    fn eval(&self, _env: &Environment, args: Vec<Value>) -> Result<Value, Error> {
+        if args.len() != 1 {
+            return Err(Error::VarEvalArgNumError { expected: Arity::Exactly(1), actual: args.len() });
+        }
+
+        match &args[0] {
+            Value::Integer(v) => Ok(Value::Integer(v.count_ones() as isize)),
+            Value::BigInt(v) if v.is_negative() => Err(Error::Overflow(format!("{v} has infinitely many one bits"))),
+            Value::BigInt(v) => Ok(Value::int(v.count_ones() as isize)),
+            v => Err(Error::VarEvalError(format!("not an integer: {v:?}"))),
+        }
+    }
+}
+
+fn shift_amount(value: &Value) -> Result<usize, Error> {
+    match value {
+        Value::Integer(v) if *v >= 0 => Ok(*v as usize),
+        Value::BigInt(v) if !v.is_negative() => Err(Error::Overflow(format!("cannot shift by {v} bits"))),
+        v => Err(Error::VarEvalError(format!("shift amount is not a non-negative integer: {v:?}"))),
+    }
+}
+
+/// `(shift-left x n)`, `x` times 2 to the power of `n`, which promotes to a big integer instead of dropping bits.
+pub struct ShiftLeft;
+
+impl Variable for ShiftLeft {
     fn eval(&self, _env: &Environment, args: Vec<Value>) -> Result<Value, Error> {
         if args.len() != 2 {
-            return Err(Error::VarEvalArgNumError { expected: 2, actual: args.len() });
+            return Err(Error::VarEvalArgNumError { expected: Arity::Exactly(2), actual: args.len() });
         }
-        match (&args[0], &args[1]) {
-            (Value::Integer(v1), Value::Integer(v2)) => Ok(Value::bool(v1 < v2)),
-            e => Err(Error::VarEvalError(format!("cannot lt {e:?}")))
+
+        let bits = shift_amount(&args[1])?;
+
+        let shifted = match &args[0] {
+            Value::Integer(0) => Some(0),
+            Value::Integer(v) if bits < isize::BITS as usize - 1 => v.checked_mul(1 << bits),
+            _ => None,
+        };
+
+        if let Some(v) = shifted {
+            return Ok(Value::Integer(v));
+        }
+
+        let v = integer(&args[0])?;
+        if v.bits().saturating_add(bits) > MAX_BITS {
+            return Err(Error::Overflow(format!("{v} shifted left by {bits} bits is too large")));
         }
+
+        Ok(Value::bigint(v.shl(bits)))
     }
 }
 
-pub struct Gt;
+/// `(shift-right x n)`, `x` divided by 2 to the power of `n` rounding towards negative infinity,
+/// like an arithmetic shift of the two's complement representation.
+pub struct ShiftRight;
 
-impl Variable for Gt {
+impl Variable for ShiftRight {
     fn eval(&self, _env: &Environment, args: Vec<Value>) -> Result<Value, Error> {
         if args.len() != 2 {
-            return Err(Error::VarEvalArgNumError { expected: 2, actual: args.len() });
+            return Err(Error::VarEvalArgNumError { expected: Arity::Exactly(2), actual: args.len() });
         }
-        match (&args[0], &args[1]) {
-            (Value::Integer(v1), Value::Integer(v2)) => Ok(Value::bool(v1 > v2)),
-            e => Err(Error::VarEvalError(format!("cannot gt {e:?}")))
+
+        let bits = shift_amount(&args[1])?;
+
+        match &args[0] {
+            Value::Integer(v) => Ok(Value::Integer(v >> bits.min(isize::BITS as usize - 1))),
+            v => {
+                let v = integer(v)?;
+                // Every bit is shifted out, which leaves only the sign.
+                if bits >= v.bits() {
+                    return Ok(Value::Integer(if v.is_negative() { -1 } else { 0 }));
+                }
+
+                Ok(Value::bigint(v.shr(bits)))
+            },
         }
     }
 }
@@ -1,7 +1,7 @@
 
 use std::{fs::read_to_string, io::stdin, process::Command};
 
-use crate::{value::{Variable, Value}, Error};
+use crate::{bigint::BigInt, value::{Variable, Value}, Arity, Error};
 
 use super::Environment;
 
@@ -21,8 +21,8 @@ pub struct ReadLine;
 
 impl Variable for ReadLine {
     fn eval(&self, _env: &Environment, args: Vec<Value>) -> Result<Value, Error> {
-        if args.len() != 0 {
-            return Err(Error::VarEvalArgNumError { expected: 0, actual: args.len() });
+        if !args.is_empty() {
+            return Err(Error::VarEvalArgNumError { expected: Arity::Exactly(0), actual: args.len() });
         }
         
         let mut input = String::new();
@@ -37,15 +37,15 @@ pub struct ParseInt;
 impl Variable for ParseInt {
     fn eval(&self, _env: &Environment, args: Vec<Value>) -> Result<Value, Error> {
         if args.len() != 1 {
-            return Err(Error::VarEvalArgNumError { expected: 1, actual: args.len() });
+            return Err(Error::VarEvalArgNumError { expected: Arity::Exactly(1), actual: args.len() });
         }
 
         match &args[0] {
             Value::String(s) => {
-                let v: isize = s.parse().map_err(|e| Error::VarEvalError(format!("error parsing {s}: {e}")))?;
-                Ok(Value::int(v))
+                let v = BigInt::parse(s.trim()).ok_or_else(|| Error::VarEvalError(format!("error parsing {s}: invalid digit found in string")))?;
+                Ok(Value::bigint(v))
             },
-            v@Value::Integer(_) => Ok(v.clone()), // TODO probably possible without cloning...
+            v@(Value::Integer(_) | Value::BigInt(_)) => Ok(v.clone()), // TODO probably possible without cloning...
             e => Err(Error::VarEvalError(format!("cannot evaluate {e:?} to int")))
         }        
     }
@@ -56,7 +56,7 @@ pub struct System;
 impl Variable for System {
     fn eval(&self, _env: &Environment, args: Vec<Value>) -> Result<Value, Error> {
         if args.len() != 1 {
-            return Err(Error::VarEvalArgNumError { expected: 1, actual: args.len() });
+            return Err(Error::VarEvalArgNumError { expected: Arity::Exactly(1), actual: args.len() });
         }
 
         let cmd = args[0].require_str()?;
@@ -64,7 +64,7 @@ impl Variable for System {
         command.arg("-c").arg(cmd);
         let output = command.output().map_err(|e| Error::VarEvalError(format!("problem executing {cmd}: {e}")))?;
         let val = String::from_utf8_lossy(&output.stdout);
-        return Ok(Value::String(val.trim_end().to_string()));
+        Ok(Value::String(val.trim_end().to_string()))
     }
 }
 
@@ -73,7 +73,7 @@ pub struct ReadFile;
 impl Variable for ReadFile {
     fn eval(&self, _env: &Environment, args: Vec<Value>) -> Result<Value, Error> {
         if args.len() != 1 {
-            return Err(Error::VarEvalArgNumError { expected: 1, actual: args.len() });
+            return Err(Error::VarEvalArgNumError { expected: Arity::Exactly(1), actual: args.len() });
         }
 
         let file_path = args[0].require_str()?;
@@ -1,25 +1,13 @@
 
-use crate::{value::{Variable, Value}, Error};
+use crate::{value::{Variable, Value}, Arity, Error};
 
 use super::Environment;
 
-pub struct Begin;
-
-impl Variable for Begin {
-    fn eval(&self, _env: &Environment, mut args: Vec<Value>) -> Result<Value, Error> {
-        if let Some(v) = args.last_mut() {
-            Ok(std::mem::take(v))
-        } else {
-            Ok(Value::Unit)
-        }
-    }
-}
-
 pub struct MkList;
 
 impl Variable for MkList {
     fn eval(&self, _env: &Environment, args: Vec<Value>) -> Result<Value, Error> {
-        Ok(Value::List(args))
+        Ok(Value::List(args.into()))
     }
 }
 
@@ -28,14 +16,13 @@ pub struct Car;
 impl Variable for Car {
     fn eval(&self, _env: &Environment, args: Vec<Value>) -> Result<Value, Error> {
         if args.len() != 1 {
-            return Err(Error::VarEvalArgNumError { expected: 1, actual: args.len() });
+            return Err(Error::VarEvalArgNumError { expected: Arity::Exactly(1), actual: args.len() });
+        }
+        match &args[0] {
+            Value::Pair(pair) => Ok(pair.first().clone()),
+            Value::List(list) => list.first().cloned().ok_or(Error::VarEvalError("list does not have an element".to_string())),
+            v => Err(Error::VarEvalError(format!("{v:?} is not a list"))),
         }
-        let first_arg = &args[0];
-        Ok(first_arg.as_list()
-            .ok_or(Error::VarEvalError(format!("{:?} is not a list", first_arg)))?
-            .get(0).ok_or(Error::VarEvalError(format!("list does not have an element")))?
-            .to_owned()
-        )
     }
 }
 
@@ -44,15 +31,14 @@ pub struct Cdr;
 impl Variable for Cdr {
     fn eval(&self, _env: &Environment, args: Vec<Value>) -> Result<Value, Error> {
         if args.len() != 1 {
-            return Err(Error::VarEvalArgNumError { expected: 1, actual: args.len() });
+            return Err(Error::VarEvalArgNumError { expected: Arity::Exactly(1), actual: args.len() });
         }
     
-        let list = args[0].as_list()
-            .ok_or(Error::VarEvalError(format!("{:?} is not a list", args[0])))?;
-
-        let list = list[1..].into_iter().map(|v| v.to_owned()).collect();
-
-        Ok(Value::List(list))
+        match &args[0] {
+            Value::Pair(pair) => Ok(pair.rest().clone()),
+            Value::List(list) => list.rest().map(Value::List).ok_or(Error::VarEvalError("list does not have an element".to_string())),
+            v => Err(Error::VarEvalError(format!("{v:?} is not a list"))),
+        }
     }
 }
 
@@ -61,15 +47,13 @@ pub struct Cons;
 impl Variable for Cons {
     fn eval(&self, _env: &Environment, mut args: Vec<Value>) -> Result<Value, Error> {
         if args.len() != 2 {
-            return Err(Error::VarEvalArgNumError { expected: 2, actual: args.len() });
+            return Err(Error::VarEvalArgNumError { expected: Arity::Exactly(2), actual: args.len() });
         }
 
         let e = std::mem::take(&mut args[0]);
         let v = std::mem::take(&mut args[1]);
-        let mut l = v.to_list().ok_or(Error::VarEvalError(format!("cdr on not a list")))?;
-        l.insert(0, e); 
 
-        Ok(Value::List(l))
+        Ok(Value::cons(e, v))
     }
 }
 
@@ -78,13 +62,15 @@ pub struct Length;
 impl Variable for Length {
     fn eval(&self, _env: &Environment, args: Vec<Value>) -> Result<Value, Error> {
         if args.len() != 1 {
-            return Err(Error::VarEvalArgNumError { expected: 1, actual: args.len() });
+            return Err(Error::VarEvalArgNumError { expected: Arity::Exactly(1), actual: args.len() });
         }
         
         let len = match &args[0] {
             Value::Unit => 0,
-            Value::String(s) => s.len(),
+            Value::String(s) => s.chars().count(),
             Value::List(l) => l.len(),
+            Value::Vector(v) => v.len(),
+            Value::Map(m) => m.len(),
             v => return Err(Error::VarEvalError(format!("{v:?} does not have a length"))),
         };
 
@@ -97,13 +83,44 @@ pub struct Endp;
 impl Variable for Endp {
     fn eval(&self, _env: &Environment, args: Vec<Value>) -> Result<Value, Error> {
         if args.len() != 1 {
-            return Err(Error::VarEvalArgNumError { expected: 1, actual: args.len() });
+            return Err(Error::VarEvalArgNumError { expected: Arity::Exactly(1), actual: args.len() });
         }
 
         match &args[0] {
             Value::List(list) => Ok(Value::bool(list.is_empty())),
             Value::Unit => Ok(Value::bool(true)),
+            Value::Pair(_) => Ok(Value::bool(false)),
             v => Err(Error::VarEvalError(format!("not a list: {v:?}"))),
         } 
     }
 }
+
+/// Whether the argument is a pair, which includes all lists that are not empty.
+pub struct IsPair;
+
+impl Variable for IsPair {
+    fn eval(&self, _env: &Environment, args: Vec<Value>) -> Result<Value, Error> {
+        if args.len() != 1 {
+            return Err(Error::VarEvalArgNumError { expected: Arity::Exactly(1), actual: args.len() });
+        }
+
+        Ok(Value::bool(match &args[0] {
+            Value::Pair(_) => true,
+            Value::List(list) => !list.is_empty(),
+            _ => false,
+        }))
+    }
+}
+
+/// Whether the argument is a proper list, which includes the empty list.
+pub struct IsList;
+
+impl Variable for IsList {
+    fn eval(&self, _env: &Environment, args: Vec<Value>) -> Result<Value, Error> {
+        if args.len() != 1 {
+            return Err(Error::VarEvalArgNumError { expected: Arity::Exactly(1), actual: args.len() });
+        }
+
+        Ok(Value::bool(matches!(&args[0], Value::List(_) | Value::Unit)))
+    }
+}
@@ -0,0 +1,133 @@
+use std::rc::Rc;
+
+use crate::{value::{MapKey, Variable, Value}, Arity, Error};
+
+use super::Environment;
+
+pub struct MkMap;
+
+impl Variable for MkMap {
+    fn eval(&self, _env: &Environment, args: Vec<Value>) -> Result<Value, Error> {
+        Value::map_of_entries(args)
+    }
+}
+
+pub struct Get;
+
+impl Variable for Get {
+    fn eval(&self, _env: &Environment, args: Vec<Value>) -> Result<Value, Error> {
+        if !(2..=3).contains(&args.len()) {
+            return Err(Error::VarEvalArgNumError { expected: Arity::Between(2, 3), actual: args.len() });
+        }
+
+        let mut args = args.into_iter();
+        let map = args.next().unwrap_or_default();
+        let key = MapKey::new(args.next().unwrap_or_default())?;
+        let default = args.next().unwrap_or_default();
+
+        Ok(map.require_map()?.get(&key).cloned().unwrap_or(default))
+    }
+}
+
+/// Sets `key` to `value` in `map`, copying its entries only if the map is shared.
+fn set(map: &mut Value, key: Value, value: Value) -> Result<(), Error> {
+    let key = MapKey::new(key)?;
+    let Value::Map(entries) = map else {
+        return Err(Error::VarEvalError(format!("not a map: {map:?}")));
+    };
+    Rc::make_mut(entries).insert(key, value);
+    Ok(())
+}
+
+/// Removes `key` from `map`, copying its entries only if the map is shared.
+fn remove(map: &mut Value, key: Value) -> Result<(), Error> {
+    let key = MapKey::new(key)?;
+    let Value::Map(entries) = map else {
+        return Err(Error::VarEvalError(format!("not a map: {map:?}")));
+    };
+    Rc::make_mut(entries).remove(&key);
+    Ok(())
+}
+
+/// Update of `hash-set!`, whose arguments are the key and the value.
+pub(crate) fn set_in_place(map: &mut Value, args: Vec<Value>) -> Result<(), Error> {
+    let mut args = args.into_iter();
+    set(map, args.next().unwrap_or_default(), args.next().unwrap_or_default())
+}
+
+/// Update of `hash-remove!`, whose argument is the key.
+pub(crate) fn remove_in_place(map: &mut Value, args: Vec<Value>) -> Result<(), Error> {
+    remove(map, args.into_iter().next().unwrap_or_default())
+}
+
+pub struct Set;
+
+impl Variable for Set {
+    fn eval(&self, _env: &Environment, args: Vec<Value>) -> Result<Value, Error> {
+        if args.len() != 3 {
+            return Err(Error::VarEvalArgNumError { expected: Arity::Exactly(3), actual: args.len() });
+        }
+
+        let mut args = args.into_iter();
+        let mut map = args.next().unwrap_or_default();
+        set(&mut map, args.next().unwrap_or_default(), args.next().unwrap_or_default())?;
+
+        Ok(map)
+    }
+}
+
+pub struct Remove;
+
+impl Variable for Remove {
+    fn eval(&self, _env: &Environment, args: Vec<Value>) -> Result<Value, Error> {
+        if args.len() != 2 {
+            return Err(Error::VarEvalArgNumError { expected: Arity::Exactly(2), actual: args.len() });
+        }
+
+        let mut args = args.into_iter();
+        let mut map = args.next().unwrap_or_default();
+        remove(&mut map, args.next().unwrap_or_default())?;
+
+        Ok(map)
+    }
+}
+
+pub struct Has;
+
+impl Variable for Has {
+    fn eval(&self, _env: &Environment, args: Vec<Value>) -> Result<Value, Error> {
+        if args.len() != 2 {
+            return Err(Error::VarEvalArgNumError { expected: Arity::Exactly(2), actual: args.len() });
+        }
+
+        #[allow(clippy::mutable_key_type)] // MapKey::new rejects lambdas and macros, the only values with interior mutability
+        let map = args[0].require_map()?;
+        let key = MapKey::new(args[1].clone())?;
+
+        Ok(Value::bool(map.contains_key(&key)))
+    }
+}
+
+pub struct Keys;
+
+impl Variable for Keys {
+    fn eval(&self, _env: &Environment, args: Vec<Value>) -> Result<Value, Error> {
+        if args.len() != 1 {
+            return Err(Error::VarEvalArgNumError { expected: Arity::Exactly(1), actual: args.len() });
+        }
+
+        Ok(args[0].require_map()?.keys().map(|k| k.value().clone()).collect())
+    }
+}
+
+pub struct Values;
+
+impl Variable for Values {
+    fn eval(&self, _env: &Environment, args: Vec<Value>) -> Result<Value, Error> {
+        if args.len() != 1 {
+            return Err(Error::VarEvalArgNumError { expected: Arity::Exactly(1), actual: args.len() });
+        }
+
+        Ok(args[0].require_map()?.values().cloned().collect())
+    }
+}
@@ -1,5 +1,5 @@
 
-use crate::{value::{Variable, Value}, Error};
+use crate::{value::{Variable, Value}, Arity, Error};
 
 use super::{Env, Environment};
 
@@ -21,12 +21,21 @@ impl TypeOf {
     pub fn type_str(value: &Value) -> &'static str {
         match value {
             Value::Unit => "unit",
+            Value::Bool(_) => "bool",
+            Value::Char(_) => "char",
             Value::String(_) => "string",
-            Value::Integer(_) => "integer",
+            Value::Integer(_) | Value::BigInt(_) => "integer",
+            Value::Ratio(_) => "ratio",
             Value::Float(_) => "float",
             Value::List(_) => "list",
+            Value::Pair(_) => "pair",
+            Value::Vector(_) => "vector",
+            Value::Map(_) => "map",
             Value::Lambda(_) => "lambda",
+            Value::Macro(_) => "macro",
+            Value::Native(_) => "native",
             Value::SymbolRef(_) => "symbolref",
+            Value::Keyword(_) => "keyword",
             Value::Error(_) => "error",
         }
     }
@@ -56,3 +65,41 @@ impl<const LOCAL_ONLY: bool> Variable for DumpEnv<LOCAL_ONLY> {
         Ok(vars.iter().map(Value::string).collect())
     }
 }
+
+pub struct MacroExpand<const ONCE: bool>;
+
+impl<const ONCE: bool> MacroExpand<ONCE> {
+
+    /// Expands `form` once if its head names a macro in `env`.
+    /// Returns `None` if `form` is not a macro call.
+    fn expand_once(env: &Environment, form: &Value) -> Result<Option<Value>, Error> {
+        let Some((Value::SymbolRef(head), args)) = form.as_list().and_then(|l| l.split_first()) else {
+            return Ok(None);
+        };
+
+        match env.get_var(head).and_then(|var| var.val()) {
+            Some(Value::Macro(m)) => m.eval(env, args.into_iter().collect()).map(Some),
+            _ => Ok(None),
+        }
+    }
+
+}
+
+impl<const ONCE: bool> Variable for MacroExpand<ONCE> {
+    fn eval(&self, env: &Environment, args: Vec<Value>) -> Result<Value, Error> {
+        if args.len() != 1 {
+            return Err(Error::VarEvalArgNumError { expected: Arity::Exactly(1), actual: args.len() });
+        }
+
+        let mut form = args.into_iter().next().unwrap();
+
+        while let Some(expansion) = Self::expand_once(env, &form)? {
+            form = expansion;
+            if ONCE {
+                break;
+            }
+        }
+
+        Ok(form)
+    }
+}
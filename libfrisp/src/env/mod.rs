@@ -1,43 +1,108 @@
-use std::{collections::HashMap, rc::Rc};
+use std::{cell::{Cell, RefCell}, collections::HashMap, fmt::Debug, rc::{Rc, Weak}};
 
-use crate::value::{ConstVal, Value, Variable};
+use crate::{eval::DEFAULT_MAX_DEPTH, value::{ConstVal, Value, Variable, WeakLambda}, Error};
 
 pub mod arithmetic;
 pub mod list;
+pub mod map;
 pub mod misc;
+pub mod numeric;
 pub mod io;
 pub mod logical;
 pub mod string;
+pub mod vector;
 
 pub trait Env {
-    fn get_var(&self, name: &str) -> Option<&Rc<dyn Variable>>;
+    fn get_var(&self, name: &str) -> Option<Rc<dyn Variable>>;
 
     fn insert_var(&mut self, name: impl ToString, var: impl Variable + 'static);
 
-    fn local_vars(&self) -> Vec<&String>;
+    /// Replaces the nearest existing binding of `name` along the scope chain.
+    /// Returns `false` if `name` is not bound.
+    fn set_var(&mut self, name: &str, var: impl Variable + 'static) -> bool;
 
-    fn all_vars(&self) -> Vec<&String>;
+    fn local_vars(&self) -> Vec<String>;
+
+    fn all_vars(&self) -> Vec<String>;
+}
+
+#[derive(Default)]
+struct Scope {
+    vars: RefCell<HashMap<String, Rc<dyn Variable>>>,
+    parent: Option<Rc<Scope>>,
 }
 
+/// Handle to a scope in the scope chain.
+///
+/// Cloning an `Environment` shares the underlying scope instead of copying it, so a lambda
+/// can hold on to the scope it was created in and still see definitions made there later.
 #[derive(Clone)]
-pub struct Environment<'a> {
-    env: HashMap<String, Rc<dyn Variable>>,
-    parent_env: Option<&'a Environment<'a>>
+pub struct Environment {
+    scope: Rc<Scope>,
+    max_depth: Rc<Cell<usize>>,
+}
+
+/// Handle to a scope that does not keep it alive, see [`Environment::downgrade`].
+pub(crate) struct WeakEnvironment {
+    scope: Weak<Scope>,
+    max_depth: Rc<Cell<usize>>,
+}
+
+impl WeakEnvironment {
+
+    /// Returns the environment, or `None` if its scope is gone.
+    pub(crate) fn upgrade(&self) -> Option<Environment> {
+        Some(Environment { scope: self.scope.upgrade()?, max_depth: self.max_depth.clone() })
+    }
+
+}
+
+/// Binding of a lambda or macro in the scope it closes over.
+///
+/// The lambda only holds on to that scope weakly, as a strong reference would be a cycle that
+/// keeps the scope alive after the call that created it has returned.
+struct WeakBinding {
+    lambda: WeakLambda,
+    is_macro: bool,
+}
+
+impl Variable for WeakBinding {
+    fn eval(&self, env: &Environment, args: Vec<Value>) -> Result<Value, Error> {
+        let value = self.val().ok_or_else(|| Error::EvalError("scope of lambda is gone".to_string()))?;
+        ConstVal::from(value).eval(env, args)
+    }
+
+    fn val(&self) -> Option<Value> {
+        let lambda = Rc::new(self.lambda.upgrade()?);
+        Some(if self.is_macro { Value::Macro(lambda) } else { Value::Lambda(lambda) })
+    }
 }
 
-impl Default for Environment<'_> {
+impl Default for Environment {
     fn default() -> Self {
-        Environment::empty()
+        Environment { scope: Default::default(), max_depth: Rc::new(Cell::new(DEFAULT_MAX_DEPTH)) }
     }
 }
 
-impl<'a> Environment<'a> {
+impl Debug for Environment {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("Environment").field(&Rc::as_ptr(&self.scope)).finish()
+    }
+}
 
-    pub fn empty() -> Environment<'a> {
-        Environment { env: Default::default(), parent_env: None }
+impl PartialEq for Environment {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.scope, &other.scope)
     }
+}
 
-    pub fn with_default_content() -> Environment<'a> {
+impl Environment {
+
+    pub fn empty() -> Environment {
+        Environment::default()
+    }
+
+    pub fn with_default_content() -> Environment {
         let mut env = Self::default();
 
         env.insert_var("+", arithmetic::Add);
@@ -45,23 +110,86 @@ impl<'a> Environment<'a> {
         env.insert_var("*", arithmetic::Mul);
         env.insert_var("/", arithmetic::Div);
         env.insert_var("mod", arithmetic::Mod);
+        env.insert_var("quotient", arithmetic::Quotient);
+        env.insert_var("remainder", arithmetic::Remainder);
+        env.insert_var("modulo", arithmetic::Modulo);
+        env.insert_var("gcd", arithmetic::Gcd);
+        env.insert_var("lcm", arithmetic::Lcm);
+        env.insert_var("bit-and", arithmetic::BitAnd);
+        env.insert_var("bit-or", arithmetic::BitOr);
+        env.insert_var("bit-xor", arithmetic::BitXor);
+        env.insert_var("bit-not", arithmetic::BitNot);
+        env.insert_var("shift-left", arithmetic::ShiftLeft);
+        env.insert_var("shift-right", arithmetic::ShiftRight);
+        env.insert_var("popcount", arithmetic::Popcount);
+        env.insert_var("+%", arithmetic::WrappingAdd);
+        env.insert_var("-%", arithmetic::WrappingSub);
+        env.insert_var("*%", arithmetic::WrappingMul);
+        env.insert_var("+|", arithmetic::SaturatingAdd);
+        env.insert_var("-|", arithmetic::SaturatingSub);
+        env.insert_var("*|", arithmetic::SaturatingMul);
         env.insert_var("==", arithmetic::Eq);
-        env.insert_var("<", arithmetic::Lt);
-        env.insert_var(">", arithmetic::Gt);
+        env.insert_var("equal?", arithmetic::Eq);
+        env.insert_var("numerator", arithmetic::Numerator);
+        env.insert_var("denominator", arithmetic::Denominator);
+        env.insert_var("exact->inexact", arithmetic::ExactToInexact);
+
+        env.insert_var("=", numeric::NumEq);
+        env.insert_var("!=", numeric::NumNe);
+        env.insert_var("<", numeric::Lt);
+        env.insert_var(">", numeric::Gt);
+        env.insert_var("<=", numeric::Le);
+        env.insert_var(">=", numeric::Ge);
+        env.insert_var("min", numeric::Min);
+        env.insert_var("max", numeric::Max);
+        env.insert_var("abs", numeric::Abs);
+        env.insert_var("floor", numeric::Floor);
+        env.insert_var("ceil", numeric::Ceil);
+        env.insert_var("round", numeric::Round);
+        env.insert_var("truncate", numeric::Truncate);
+        env.insert_var("sqrt", numeric::FloatFn(f64::sqrt));
+        env.insert_var("exp", numeric::FloatFn(f64::exp));
+        env.insert_var("log", numeric::Log);
+        env.insert_var("expt", numeric::Expt);
+        env.insert_var("sin", numeric::FloatFn(f64::sin));
+        env.insert_var("cos", numeric::FloatFn(f64::cos));
+        env.insert_var("tan", numeric::FloatFn(f64::tan));
+        env.insert_var("asin", numeric::FloatFn(f64::asin));
+        env.insert_var("acos", numeric::FloatFn(f64::acos));
+        env.insert_var("atan", numeric::FloatFn(f64::atan));
+        env.insert_var("integer?", numeric::IsInteger);
+        env.insert_var("float?", numeric::IsFloat);
+        env.insert_var("nan?", numeric::IsNan);
 
         env.insert_var("not", logical::Not);
-        env.insert_var("or", logical::Or);
-        env.insert_var("and", logical::And);
 
         env.insert_var("pi", ConstVal::from(Value::Float(std::f64::consts::PI)));
         
-        env.insert_var("begin", list::Begin);
         env.insert_var("list", list::MkList);
         env.insert_var("car", list::Car);
         env.insert_var("cdr", list::Cdr);
         env.insert_var("cons", list::Cons);
         env.insert_var("length", list::Length);
         env.insert_var("endp", list::Endp);
+        env.insert_var("pair?", list::IsPair);
+        env.insert_var("list?", list::IsList);
+
+        env.insert_var("hash-map", map::MkMap);
+        env.insert_var("hash-get", map::Get);
+        env.insert_var("hash-set", map::Set);
+        env.insert_var("hash-remove", map::Remove);
+        env.insert_var("hash-has", map::Has);
+        env.insert_var("hash-keys", map::Keys);
+        env.insert_var("hash-values", map::Values);
+
+        env.insert_var("vector", vector::MkVector);
+        env.insert_var("vector-ref", vector::Ref);
+        env.insert_var("vector-set", vector::Set);
+        env.insert_var("vector-length", vector::Length);
+        env.insert_var("vector-push", vector::Push);
+        env.insert_var("subvector", vector::SubVector);
+        env.insert_var("vector->list", vector::ToList);
+        env.insert_var("list->vector", vector::FromList);
 
         env.insert_var("read-line", io::ReadLine);
         env.insert_var("read-file", io::ReadFile);
@@ -74,52 +202,162 @@ impl<'a> Environment<'a> {
         env.insert_var("str-concat", string::Concat);
         env.insert_var("str-join", string::Join);
         env.insert_var("to-string", string::ToString);
+        env.insert_var("string-ref", string::Ref);
+        env.insert_var("string->list", string::ToList);
+        env.insert_var("list->string", string::FromList);
+        env.insert_var("char->integer", string::CharToInt);
+        env.insert_var("integer->char", string::IntToChar);
+        env.insert_var("byte-length", string::ByteLength);
 
         env.insert_var("debug", misc::DebugPrint);
         env.insert_var("type-of", misc::TypeOf);
         env.insert_var("local-env", misc::DumpEnv::<true>);
         env.insert_var("global-env", misc::DumpEnv::<false>);
+        env.insert_var("macroexpand-1", misc::MacroExpand::<true>);
+        env.insert_var("macroexpand", misc::MacroExpand::<false>);
         
         env
     }
 
-    pub fn sub_env(&'a self) -> Environment<'a> {
-        Environment { env: Default::default(), parent_env: Some(self) }
+    pub fn sub_env(&self) -> Environment {
+        let scope = Scope { vars: Default::default(), parent: Some(self.scope.clone()) };
+        Environment { scope: Rc::new(scope), max_depth: self.max_depth.clone() }
     }
 
-    pub fn local_env(&'a self) -> Environment<'a> {
-        // TODO think about how this should work really
-        self.clone()
+    /// Looks up `name`, following bindings to symbols until one is bound to something else.
+    /// Fails if a symbol is unbound or the symbols refer to each other in a cycle.
+    pub fn resolve_var(&self, name: &str) -> Result<Rc<dyn Variable>, Error> {
+        let mut chain = vec![name.to_string()];
+        loop {
+            let name = &chain[chain.len() - 1];
+            let var = self.get_var(name)
+                .ok_or_else(|| Error::VarEvalError(format!("unknown symbol: {name}")))?;
+            let Some(Value::SymbolRef(next)) = var.val() else {
+                return Ok(var);
+            };
+            if chain.contains(&next) {
+                chain.push(next);
+                return Err(Error::EvalError(format!("cyclic symbol reference: {}", chain.join(" -> "))));
+            }
+            chain.push(next);
+        }
+    }
+
+    /// Binds `name` to `value` in this scope.
+    ///
+    /// Unlike [`Env::insert_var`] with a [`ConstVal`], a lambda or macro that closes over this
+    /// scope does not keep it alive. Lambdas in lists, vectors or maps still do, so a scope that
+    /// holds a data structure with a lambda closing over it is never freed.
+    pub fn insert_value(&mut self, name: impl ToString, value: Value) {
+        let var = Self::binding(&self.scope, value);
+        self.scope.vars.borrow_mut().insert(name.to_string(), var);
+    }
+
+    /// Replaces the nearest existing binding of `name` with `value`, like [`Env::set_var`].
+    /// Returns `false` if `name` is not bound.
+    pub fn set_value(&mut self, name: &str, value: Value) -> bool {
+        let mut scope = Some(&self.scope);
+        while let Some(s) = scope {
+            if let Some(binding) = s.vars.borrow_mut().get_mut(name) {
+                *binding = Self::binding(s, value);
+                return true;
+            }
+            scope = s.parent.as_ref();
+        }
+        false
+    }
+
+    /// Changes the value of the nearest existing binding of `name` in place with `update`.
+    ///
+    /// The binding is taken out while `update` runs, so that a vector or map that is only bound
+    /// to `name` is not copied when it is changed.
+    pub fn update_value(&self, name: &str, update: impl FnOnce(&mut Value) -> Result<(), Error>) -> Result<(), Error> {
+        let mut scope = Some(&self.scope);
+        while let Some(s) = scope {
+            if let Some(binding) = s.vars.borrow_mut().get_mut(name) {
+                let Some(mut value) = binding.val() else {
+                    return Err(Error::EvalError(format!("cannot update builtin: {name}")));
+                };
+                *binding = Rc::new(ConstVal::from(Value::Unit));
+                let result = update(&mut value);
+                *binding = Self::binding(s, value);
+                return result;
+            }
+            scope = s.parent.as_ref();
+        }
+        Err(Error::EvalError(format!("cannot update undefined variable: {name}")))
+    }
+
+    /// Wraps `value` for binding it in `scope`, see [`Environment::insert_value`].
+    fn binding(scope: &Rc<Scope>, value: Value) -> Rc<dyn Variable> {
+        match &value {
+            Value::Lambda(lambda) | Value::Macro(lambda) if Rc::ptr_eq(&lambda.env().scope, scope) => {
+                let is_macro = matches!(value, Value::Macro(_));
+                Rc::new(WeakBinding { lambda: lambda.downgrade(), is_macro })
+            },
+            _ => Rc::new(ConstVal::from(value)),
+        }
+    }
+
+    /// Returns a handle to the scope that does not keep it alive.
+    pub(crate) fn downgrade(&self) -> WeakEnvironment {
+        WeakEnvironment { scope: Rc::downgrade(&self.scope), max_depth: self.max_depth.clone() }
+    }
+
+    /// Maximum number of frames on the evaluation stack before evaluation fails with
+    /// [`Error::RecursionLimitError`](crate::Error::RecursionLimitError).
+    pub fn max_depth(&self) -> usize {
+        self.max_depth.get()
+    }
+
+    /// Sets the recursion limit for this environment and all environments derived from it.
+    pub fn set_max_depth(&self, max_depth: usize) {
+        self.max_depth.set(max_depth);
     }
 
 }
 
-impl<'a> Env for Environment<'a> {
-    fn get_var(&self, name: &str) -> Option<&Rc<dyn Variable>> {
+impl Env for Environment {
+    fn get_var(&self, name: &str) -> Option<Rc<dyn Variable>> {
         #[cfg(feature = "log")]
-        println!("looking up {name} in env#{self:p}");
-        self.env.get(name).or_else(|| self.parent_env.and_then(|pe| {
-            pe.get_var(name)
-        }))
+        println!("looking up {name} in env#{:p}", Rc::as_ptr(&self.scope));
+        let mut scope = Some(&self.scope);
+        while let Some(s) = scope {
+            if let Some(var) = s.vars.borrow().get(name) {
+                return Some(var.clone());
+            }
+            scope = s.parent.as_ref();
+        }
+        None
     }
 
     fn insert_var(&mut self, name: impl ToString, var: impl Variable + 'static) {
-        self.env.insert(name.to_string(), Rc::new(var));
+        self.scope.vars.borrow_mut().insert(name.to_string(), Rc::new(var));
     }
 
-    fn local_vars(&self) -> Vec<&String> {
-        self.env.keys().collect()        
+    fn set_var(&mut self, name: &str, var: impl Variable + 'static) -> bool {
+        let mut scope = Some(&self.scope);
+        while let Some(s) = scope {
+            if let Some(binding) = s.vars.borrow_mut().get_mut(name) {
+                *binding = Rc::new(var);
+                return true;
+            }
+            scope = s.parent.as_ref();
+        }
+        false
     }
 
-    fn all_vars(&self) -> Vec<&String> {
-        match self.parent_env {
-            Some(pe) => {
-                let mut vars = self.local_vars();
-                vars.append(&mut pe.all_vars());
-                vars
-            },
-            None => self.local_vars(),
+    fn local_vars(&self) -> Vec<String> {
+        self.scope.vars.borrow().keys().cloned().collect()
+    }
+
+    fn all_vars(&self) -> Vec<String> {
+        let mut vars = Vec::new();
+        let mut scope = Some(&self.scope);
+        while let Some(s) = scope {
+            vars.extend(s.vars.borrow().keys().cloned());
+            scope = s.parent.as_ref();
         }
+        vars
     }
 }
-
@@ -0,0 +1,325 @@
+use std::cmp::Ordering;
+
+use crate::{bigint::{BigInt, MAX_BITS}, ratio::Ratio, value::{Operands, Variable, Value}, Arity, Error};
+
+use super::Environment;
+
+/// Compares two numbers by value regardless of their representation, so `1` equals `1.0`.
+/// Returns `None` if either is NaN.
+pub fn compare(v1: &Value, v2: &Value) -> Result<Option<Ordering>, Error> {
+    let exact = |v: &Value| v.to_ratio().unwrap_or_else(|| unreachable!("operands are numbers"));
+
+    match Operands::of(v1, v2) {
+        Some(Operands::Int(a, b)) => Ok(Some(a.cmp(&b))),
+        Some(Operands::Big(a, b)) => Ok(Some(a.cmp(&b))),
+        Some(Operands::Ratio(a, b)) => Ok(Some(a.cmp(&b))),
+        Some(Operands::Float(a, b)) => match (v1, v2) {
+            (Value::Float(_), Value::Float(_)) => Ok(a.partial_cmp(&b)),
+            (Value::Float(_), v2) => Ok(compare_exact(exact(v2), a).map(Ordering::reverse)),
+            (v1, _) => Ok(compare_exact(exact(v1), b)),
+        },
+        None => Err(Error::VarEvalError(format!("cannot compare {v1:?} and {v2:?}"))),
+    }
+}
+
+/// Compares an exact number to a float without rounding the exact number to a float first,
+/// which could make different numbers equal.
+fn compare_exact(exact: Ratio, float: f64) -> Option<Ordering> {
+    match Ratio::from_f64(float) {
+        Some(float) => Some(exact.cmp(&float)),
+        // Exact numbers are finite, so they compare to infinities and NaN like zero does.
+        None => 0.0.partial_cmp(&float),
+    }
+}
+
+/// Checks that `holds` is true for every two neighboring arguments, so `(< a b c)` means `a < b` and `b < c`.
+fn chain(args: &[Value], holds: fn(Option<Ordering>) -> bool) -> Result<Value, Error> {
+    if args.is_empty() {
+        return Err(Error::VarEvalArgNumError { expected: Arity::AtLeast(1), actual: args.len() });
+    }
+
+    if let [single] = args {
+        compare(single, single)?;
+    }
+
+    let mut result = true;
+    for pair in args.windows(2) {
+        result &= holds(compare(&pair[0], &pair[1])?);
+    }
+
+    Ok(Value::bool(result))
+}
+
+/// `=`, numeric equality, unlike `equal?` which compares structurally.
+pub struct NumEq;
+
+impl Variable for NumEq {
+    fn eval(&self, _env: &Environment, args: Vec<Value>) -> Result<Value, Error> {
+        chain(&args, |o| o == Some(Ordering::Equal))
+    }
+}
+
+/// `!=`, true if no two neighboring arguments are numerically equal.
+pub struct NumNe;
+
+impl Variable for NumNe {
+    fn eval(&self, _env: &Environment, args: Vec<Value>) -> Result<Value, Error> {
+        chain(&args, |o| o != Some(Ordering::Equal))
+    }
+}
+
+pub struct Lt;
+
+impl Variable for Lt {
+    fn eval(&self, _env: &Environment, args: Vec<Value>) -> Result<Value, Error> {
+        chain(&args, |o| o == Some(Ordering::Less))
+    }
+}
+
+pub struct Gt;
+
+impl Variable for Gt {
+    fn eval(&self, _env: &Environment, args: Vec<Value>) -> Result<Value, Error> {
+        chain(&args, |o| o == Some(Ordering::Greater))
+    }
+}
+
+pub struct Le;
+
+impl Variable for Le {
+    fn eval(&self, _env: &Environment, args: Vec<Value>) -> Result<Value, Error> {
+        chain(&args, |o| matches!(o, Some(Ordering::Less | Ordering::Equal)))
+    }
+}
+
+pub struct Ge;
+
+impl Variable for Ge {
+    fn eval(&self, _env: &Environment, args: Vec<Value>) -> Result<Value, Error> {
+        chain(&args, |o| matches!(o, Some(Ordering::Greater | Ordering::Equal)))
+    }
+}
+
+/// Returns the argument that compares as `wanted` to all others, or NaN if any argument is NaN.
+fn extreme(args: Vec<Value>, wanted: Ordering) -> Result<Value, Error> {
+    let mut args = args.into_iter();
+    let mut result = args.next().ok_or(Error::VarEvalArgNumError { expected: Arity::AtLeast(1), actual: 0 })?;
+
+    compare(&result, &result)?;
+
+    for arg in args {
+        match compare(&arg, &result)? {
+            Some(o) if o == wanted => result = arg,
+            Some(_) => {},
+            None => return Ok(Value::Float(f64::NAN)),
+        }
+    }
+
+    Ok(result)
+}
+
+pub struct Min;
+
+impl Variable for Min {
+    fn eval(&self, _env: &Environment, args: Vec<Value>) -> Result<Value, Error> {
+        extreme(args, Ordering::Less)
+    }
+}
+
+pub struct Max;
+
+impl Variable for Max {
+    fn eval(&self, _env: &Environment, args: Vec<Value>) -> Result<Value, Error> {
+        extreme(args, Ordering::Greater)
+    }
+}
+
+pub struct Abs;
+
+impl Variable for Abs {
+    fn eval(&self, _env: &Environment, args: Vec<Value>) -> Result<Value, Error> {
+        if args.len() != 1 {
+            return Err(Error::VarEvalArgNumError { expected: Arity::Exactly(1), actual: args.len() });
+        }
+
+        match &args[0] {
+            Value::Integer(v) => Ok(v.checked_abs().map_or_else(|| Value::bigint(BigInt::from(*v).abs()), Value::Integer)),
+            Value::BigInt(v) => Ok(Value::bigint(v.abs())),
+            Value::Ratio(v) => Ok(Value::ratio(v.abs())),
+            Value::Float(v) => Ok(Value::Float(v.abs())),
+            v => Err(Error::VarEvalError(format!("not a number: {v:?}"))),
+        }
+    }
+}
+
+/// Rounds a ratio to an integer and a float to an integral float. Integers are returned as is.
+fn round_with(args: Vec<Value>, ratio: fn(&Ratio) -> BigInt, float: fn(f64) -> f64) -> Result<Value, Error> {
+    if args.len() != 1 {
+        return Err(Error::VarEvalArgNumError { expected: Arity::Exactly(1), actual: args.len() });
+    }
+
+    match &args[0] {
+        v@(Value::Integer(_) | Value::BigInt(_)) => Ok(v.clone()),
+        Value::Ratio(v) => Ok(Value::bigint(ratio(v))),
+        Value::Float(v) => Ok(Value::Float(float(*v))),
+        v => Err(Error::VarEvalError(format!("not a number: {v:?}"))),
+    }
+}
+
+pub struct Floor;
+
+impl Variable for Floor {
+    fn eval(&self, _env: &Environment, args: Vec<Value>) -> Result<Value, Error> {
+        round_with(args, Ratio::floor, f64::floor)
+    }
+}
+
+pub struct Ceil;
+
+impl Variable for Ceil {
+    fn eval(&self, _env: &Environment, args: Vec<Value>) -> Result<Value, Error> {
+        round_with(args, Ratio::ceil, f64::ceil)
+    }
+}
+
+/// Rounds to the nearest integer, and halfway cases away from zero.
+pub struct Round;
+
+impl Variable for Round {
+    fn eval(&self, _env: &Environment, args: Vec<Value>) -> Result<Value, Error> {
+        round_with(args, Ratio::round, f64::round)
+    }
+}
+
+pub struct Truncate;
+
+impl Variable for Truncate {
+    fn eval(&self, _env: &Environment, args: Vec<Value>) -> Result<Value, Error> {
+        round_with(args, Ratio::trunc, f64::trunc)
+    }
+}
+
+/// A function on floats, which converts its argument to a float first.
+pub struct FloatFn(pub fn(f64) -> f64);
+
+impl Variable for FloatFn {
+    fn eval(&self, _env: &Environment, args: Vec<Value>) -> Result<Value, Error> {
+        if args.len() != 1 {
+            return Err(Error::VarEvalArgNumError { expected: Arity::Exactly(1), actual: args.len() });
+        }
+
+        let v = args[0].to_f64().ok_or_else(|| Error::VarEvalError(format!("not a number: {:?}", args[0])))?;
+
+        Ok(Value::Float(self.0(v)))
+    }
+}
+
+/// `(log x [base])`, the logarithm of `x` to `base`, which defaults to e.
+pub struct Log;
+
+impl Variable for Log {
+    fn eval(&self, _env: &Environment, args: Vec<Value>) -> Result<Value, Error> {
+        if !(1..=2).contains(&args.len()) {
+            return Err(Error::VarEvalArgNumError { expected: Arity::Between(1, 2), actual: args.len() });
+        }
+
+        let floats: Result<Vec<_>, _> = args.iter()
+            .map(|v| v.to_f64().ok_or_else(|| Error::VarEvalError(format!("not a number: {v:?}"))))
+            .collect();
+
+        match floats?[..] {
+            [x] => Ok(Value::Float(x.ln())),
+            [x, base] => Ok(Value::Float(x.log(base))),
+            _ => unreachable!("arity is checked above"),
+        }
+    }
+}
+
+/// `(expt base exponent)`, which is exact if `base` is exact and `exponent` is an integer.
+pub struct Expt;
+
+impl Expt {
+
+    fn exact(base: &Value, ratio: &Ratio, exponent: isize) -> Result<Value, Error> {
+        // The magnitude of a number with n bits is at least 2 to the power of n - 1, which bounds the result from below.
+        let bits = ratio.numer().bits().max(ratio.denom().bits()).saturating_sub(1);
+        if bits.saturating_mul(exponent.unsigned_abs()) > MAX_BITS {
+            return Err(Error::Overflow(format!("{base} to the power of {exponent} is too large")));
+        }
+
+        let mut result = Value::Integer(1);
+        let mut square = base.clone();
+        let mut n = exponent.unsigned_abs();
+        while n > 0 {
+            if n & 1 == 1 {
+                result = (result * square.clone())?;
+            }
+            n >>= 1;
+            if n > 0 {
+                square = (square.clone() * square)?;
+            }
+        }
+
+        if exponent < 0 {
+            Value::Integer(1) / result
+        } else {
+            Ok(result)
+        }
+    }
+
+}
+
+impl Variable for Expt {
+    fn eval(&self, _env: &Environment, args: Vec<Value>) -> Result<Value, Error> {
+        if args.len() != 2 {
+            return Err(Error::VarEvalArgNumError { expected: Arity::Exactly(2), actual: args.len() });
+        }
+
+        let (base, exponent) = (&args[0], &args[1]);
+
+        match (base.to_ratio(), exponent) {
+            (Some(ratio), Value::Integer(exponent)) => Self::exact(base, &ratio, *exponent),
+            (Some(_), Value::BigInt(exponent)) => Err(Error::Overflow(format!("exponent {exponent} is too large"))),
+            _ => match (base.to_f64(), exponent.to_f64()) {
+                (Some(base), Some(exponent)) => Ok(Value::Float(base.powf(exponent))),
+                _ => Err(Error::VarEvalError(format!("cannot expt {base:?} and {exponent:?}"))),
+            },
+        }
+    }
+}
+
+pub struct IsInteger;
+
+impl Variable for IsInteger {
+    fn eval(&self, _env: &Environment, args: Vec<Value>) -> Result<Value, Error> {
+        if args.len() != 1 {
+            return Err(Error::VarEvalArgNumError { expected: Arity::Exactly(1), actual: args.len() });
+        }
+
+        Ok(Value::bool(matches!(args[0], Value::Integer(_) | Value::BigInt(_))))
+    }
+}
+
+pub struct IsFloat;
+
+impl Variable for IsFloat {
+    fn eval(&self, _env: &Environment, args: Vec<Value>) -> Result<Value, Error> {
+        if args.len() != 1 {
+            return Err(Error::VarEvalArgNumError { expected: Arity::Exactly(1), actual: args.len() });
+        }
+
+        Ok(Value::bool(matches!(args[0], Value::Float(_))))
+    }
+}
+
+pub struct IsNan;
+
+impl Variable for IsNan {
+    fn eval(&self, _env: &Environment, args: Vec<Value>) -> Result<Value, Error> {
+        if args.len() != 1 {
+            return Err(Error::VarEvalArgNumError { expected: Arity::Exactly(1), actual: args.len() });
+        }
+
+        args[0].to_f64().map(|v| Value::bool(v.is_nan())).ok_or_else(|| Error::VarEvalError(format!("not a number: {:?}", args[0])))
+    }
+}
@@ -1,5 +1,5 @@
 
-use crate::{value::{Variable, Value}, Error};
+use crate::{params::KeywordArgs, value::{Variable, Value}, Arity, Error};
 
 use super::Environment;
 
@@ -7,17 +7,25 @@ pub struct Split;
 
 impl Variable for Split {
     fn eval(&self, _env: &Environment, args: Vec<Value>) -> Result<Value, Error> {
+        let (args, mut keyword_args) = KeywordArgs::split(args)?;
+        let limit = keyword_args.take("limit");
+        keyword_args.finish()?;
+
         if args.len() != 2 {
-            return Err(Error::VarEvalArgNumError { expected: 2, actual: args.len() });
+            return Err(Error::VarEvalArgNumError { expected: Arity::Exactly(2), actual: args.len() });
         }
 
         let string = args[0].require_str()?;
 
         let split = args[1].require_str()?;
 
-        let parts: Vec<_> = string.split(split).map(|s| Value::string(s)).collect();
+        let parts: Vec<_> = match limit {
+            Some(Value::Integer(limit)) if limit > 0 => string.splitn(limit as usize, split).map(Value::string).collect(),
+            Some(v) => return Err(Error::VarEvalError(format!("limit is not a positive integer: {v:?}"))),
+            None => string.split(split).map(Value::string).collect(),
+        };
 
-        Ok(Value::List(parts))
+        Ok(Value::List(parts.into()))
     }
 }
 
@@ -26,14 +34,14 @@ pub struct Lines;
 impl Variable for Lines {
     fn eval(&self, _env: &Environment, args: Vec<Value>) -> Result<Value, Error> {
         if args.len() != 1 {
-            return Err(Error::VarEvalArgNumError { expected: 1, actual: args.len() });
+            return Err(Error::VarEvalArgNumError { expected: Arity::Exactly(1), actual: args.len() });
         }
 
         let string = args[0].require_str()?;
 
-        let parts: Vec<_> = string.lines().map(|s| Value::string(s)).collect();
+        let parts: Vec<_> = string.lines().map(Value::string).collect();
 
-        Ok(Value::List(parts))
+        Ok(Value::List(parts.into()))
     }
 }
 
@@ -45,15 +53,36 @@ impl ToString {
     pub fn value_to_string(value: &Value) -> Result<String, Error> {
         match value {
             Value::Unit => Ok("".to_string()),
+            Value::Bool(v) => Ok(if *v { "#t" } else { "#f" }.to_string()),
+            Value::Char(c) => Ok(c.to_string()),
             Value::String(s) => Ok(s.to_owned()),
             Value::Integer(v) => Ok(v.to_string()),
+            Value::BigInt(v) => Ok(v.to_string()),
+            Value::Ratio(v) => Ok(v.to_string()),
             Value::Float(v) => Ok(v.to_string()),
             Value::Error(e) => Ok(format!("Error: {e}")),
             Value::SymbolRef(s) => Ok(s.to_string()),
+            Value::Native(native) => Ok(native.name().to_string()),
+            Value::Keyword(s) => Ok(format!(":{s}")),
             Value::List(l) => {
-                let v: Result<Vec<_>, _> = l.iter().map(|v| Self::value_to_string(v)).collect();
+                let v: Result<Vec<_>, _> = l.iter().map(Self::value_to_string).collect();
                 Ok(format!("({})", v?.join(" ")))
             },
+            Value::Pair(p) => {
+                let (items, rest) = Value::pair_parts(p);
+                let v: Result<Vec<_>, _> = items.into_iter().map(Self::value_to_string).collect();
+                Ok(format!("({} . {})", v?.join(" "), Self::value_to_string(rest)?))
+            },
+            Value::Vector(l) => {
+                let v: Result<Vec<_>, _> = l.iter().map(Self::value_to_string).collect();
+                Ok(format!("[{}]", v?.join(" ")))
+            },
+            Value::Map(m) => {
+                let v: Result<Vec<_>, _> = m.iter()
+                    .map(|(k, v)| Ok(format!("{} {}", Self::value_to_string(k.value())?, Self::value_to_string(v)?)))
+                    .collect();
+                Ok(format!("{{{}}}", v?.join(" ")))
+            },
             v => Err(Error::VarEvalError(format!("cannot make into string: {v:?}"))),
         }
     }
@@ -80,8 +109,10 @@ impl Variable for Concat {
         let mut result = String::new();
 
         for arg in &args {
-            let str = arg.require_str()?;
-            result.push_str(str);
+            match arg {
+                Value::Char(c) => result.push(*c),
+                arg => result.push_str(arg.require_str()?),
+            }
         }
 
         Ok(Value::String(result))
@@ -93,7 +124,7 @@ pub struct Join;
 impl Variable for Join {
     fn eval(&self, _env: &Environment, args: Vec<Value>) -> Result<Value, Error> {
         if args.len() != 2 {
-            return Err(Error::VarEvalArgNumError { expected: 2, actual: args.len() });
+            return Err(Error::VarEvalArgNumError { expected: Arity::Exactly(2), actual: args.len() });
         }
 
         let sep = args[0].require_str()?;
@@ -107,3 +138,100 @@ impl Variable for Join {
     }
 }
 
+
+pub struct Ref;
+
+impl Variable for Ref {
+    fn eval(&self, _env: &Environment, args: Vec<Value>) -> Result<Value, Error> {
+        if args.len() != 2 {
+            return Err(Error::VarEvalArgNumError { expected: Arity::Exactly(2), actual: args.len() });
+        }
+
+        let string = args[0].require_str()?;
+
+        let c = match &args[1] {
+            Value::Integer(i) if *i >= 0 => string.chars().nth(*i as usize),
+            _ => None,
+        };
+
+        c.map(Value::Char).ok_or_else(|| Error::VarEvalError(format!("index {:?} out of bounds", args[1])))
+    }
+}
+
+pub struct ToList;
+
+impl Variable for ToList {
+    fn eval(&self, _env: &Environment, args: Vec<Value>) -> Result<Value, Error> {
+        if args.len() != 1 {
+            return Err(Error::VarEvalArgNumError { expected: Arity::Exactly(1), actual: args.len() });
+        }
+
+        Ok(args[0].require_str()?.chars().map(Value::Char).collect())
+    }
+}
+
+pub struct FromList;
+
+impl Variable for FromList {
+    fn eval(&self, _env: &Environment, args: Vec<Value>) -> Result<Value, Error> {
+        if args.len() != 1 {
+            return Err(Error::VarEvalArgNumError { expected: Arity::Exactly(1), actual: args.len() });
+        }
+
+        let list = args.into_iter().next().unwrap_or_default().to_list().ok_or(Error::VarEvalError("not a list".to_string()))?;
+
+        let string: Result<String, _> = list.iter()
+            .map(|v| match v {
+                Value::Char(c) => Ok(*c),
+                v => Err(Error::VarEvalError(format!("not a char: {v:?}"))),
+            })
+            .collect();
+
+        string.map(Value::String)
+    }
+}
+
+pub struct CharToInt;
+
+impl Variable for CharToInt {
+    fn eval(&self, _env: &Environment, args: Vec<Value>) -> Result<Value, Error> {
+        if args.len() != 1 {
+            return Err(Error::VarEvalArgNumError { expected: Arity::Exactly(1), actual: args.len() });
+        }
+
+        match &args[0] {
+            Value::Char(c) => Ok(Value::int(*c as isize)),
+            v => Err(Error::VarEvalError(format!("not a char: {v:?}"))),
+        }
+    }
+}
+
+pub struct IntToChar;
+
+impl Variable for IntToChar {
+    fn eval(&self, _env: &Environment, args: Vec<Value>) -> Result<Value, Error> {
+        if args.len() != 1 {
+            return Err(Error::VarEvalArgNumError { expected: Arity::Exactly(1), actual: args.len() });
+        }
+
+        let c = match &args[0] {
+            Value::Integer(i) => u32::try_from(*i).ok().and_then(char::from_u32),
+            _ => None,
+        };
+
+        c.map(Value::Char).ok_or_else(|| Error::VarEvalError(format!("not a Unicode scalar value: {:?}", args[0])))
+    }
+}
+
+/// Length of a string in bytes of its UTF-8 encoding, unlike `length` which counts characters.
+pub struct ByteLength;
+
+impl Variable for ByteLength {
+    fn eval(&self, _env: &Environment, args: Vec<Value>) -> Result<Value, Error> {
+        if args.len() != 1 {
+            return Err(Error::VarEvalArgNumError { expected: Arity::Exactly(1), actual: args.len() });
+        }
+
+        Ok(Value::int(args[0].require_str()?.len() as isize))
+    }
+}
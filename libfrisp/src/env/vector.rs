@@ -0,0 +1,161 @@
+use std::rc::Rc;
+
+use crate::{value::{Variable, Value}, Arity, Error};
+
+use super::Environment;
+
+/// Checks that `index` is an integer below `bound`.
+fn index(index: &Value, bound: usize) -> Result<usize, Error> {
+    match index {
+        Value::Integer(i) if (0..bound as isize).contains(i) => Ok(*i as usize),
+        i => Err(Error::VarEvalError(format!("index {i:?} out of bounds"))),
+    }
+}
+
+pub struct MkVector;
+
+impl Variable for MkVector {
+    fn eval(&self, _env: &Environment, args: Vec<Value>) -> Result<Value, Error> {
+        Ok(Value::Vector(Rc::new(args)))
+    }
+}
+
+pub struct Ref;
+
+impl Variable for Ref {
+    fn eval(&self, _env: &Environment, args: Vec<Value>) -> Result<Value, Error> {
+        if args.len() != 2 {
+            return Err(Error::VarEvalArgNumError { expected: Arity::Exactly(2), actual: args.len() });
+        }
+
+        let vector = args[0].require_vector()?;
+        let i = index(&args[1], vector.len())?;
+
+        Ok(vector[i].clone())
+    }
+}
+
+/// Sets the element at `i` of `vector` to `value`, copying the elements only if the vector is shared.
+fn set(vector: &mut Value, i: Value, value: Value) -> Result<(), Error> {
+    let Value::Vector(elements) = vector else {
+        return Err(Error::VarEvalError(format!("not a vector: {vector:?}")));
+    };
+    let i = index(&i, elements.len())?;
+    Rc::make_mut(elements)[i] = value;
+    Ok(())
+}
+
+/// Appends `value` to `vector`, copying the elements only if the vector is shared.
+fn push(vector: &mut Value, value: Value) -> Result<(), Error> {
+    let Value::Vector(elements) = vector else {
+        return Err(Error::VarEvalError(format!("not a vector: {vector:?}")));
+    };
+    Rc::make_mut(elements).push(value);
+    Ok(())
+}
+
+/// Update of `vector-set!`, whose arguments are the index and the value.
+pub(crate) fn set_in_place(vector: &mut Value, args: Vec<Value>) -> Result<(), Error> {
+    let mut args = args.into_iter();
+    set(vector, args.next().unwrap_or_default(), args.next().unwrap_or_default())
+}
+
+/// Update of `vector-push!`, whose argument is the value.
+pub(crate) fn push_in_place(vector: &mut Value, args: Vec<Value>) -> Result<(), Error> {
+    push(vector, args.into_iter().next().unwrap_or_default())
+}
+
+pub struct Set;
+
+impl Variable for Set {
+    fn eval(&self, _env: &Environment, args: Vec<Value>) -> Result<Value, Error> {
+        if args.len() != 3 {
+            return Err(Error::VarEvalArgNumError { expected: Arity::Exactly(3), actual: args.len() });
+        }
+
+        let mut args = args.into_iter();
+        let mut vector = args.next().unwrap_or_default();
+        set(&mut vector, args.next().unwrap_or_default(), args.next().unwrap_or_default())?;
+
+        Ok(vector)
+    }
+}
+
+pub struct Length;
+
+impl Variable for Length {
+    fn eval(&self, _env: &Environment, args: Vec<Value>) -> Result<Value, Error> {
+        if args.len() != 1 {
+            return Err(Error::VarEvalArgNumError { expected: Arity::Exactly(1), actual: args.len() });
+        }
+
+        Ok(Value::int(args[0].require_vector()?.len() as isize))
+    }
+}
+
+pub struct Push;
+
+impl Variable for Push {
+    fn eval(&self, _env: &Environment, args: Vec<Value>) -> Result<Value, Error> {
+        if args.len() != 2 {
+            return Err(Error::VarEvalArgNumError { expected: Arity::Exactly(2), actual: args.len() });
+        }
+
+        let mut args = args.into_iter();
+        let mut vector = args.next().unwrap_or_default();
+        push(&mut vector, args.next().unwrap_or_default())?;
+
+        Ok(vector)
+    }
+}
+
+/// `(subvector v start [end])`, the elements of `v` from `start` up to but excluding `end`,
+/// which defaults to the length of `v`.
+pub struct SubVector;
+
+impl Variable for SubVector {
+    fn eval(&self, _env: &Environment, args: Vec<Value>) -> Result<Value, Error> {
+        if !(2..=3).contains(&args.len()) {
+            return Err(Error::VarEvalArgNumError { expected: Arity::Between(2, 3), actual: args.len() });
+        }
+
+        let vector = args[0].require_vector()?;
+        let start = index(&args[1], vector.len() + 1)?;
+        let end = match args.get(2) {
+            Some(end) => index(end, vector.len() + 1)?,
+            None => vector.len(),
+        };
+
+        if start > end {
+            return Err(Error::VarEvalError(format!("start {start} is after end {end}")));
+        }
+
+        Ok(Value::Vector(Rc::new(vector[start..end].to_vec())))
+    }
+}
+
+pub struct ToList;
+
+impl Variable for ToList {
+    fn eval(&self, _env: &Environment, args: Vec<Value>) -> Result<Value, Error> {
+        if args.len() != 1 {
+            return Err(Error::VarEvalArgNumError { expected: Arity::Exactly(1), actual: args.len() });
+        }
+
+        Ok(args[0].require_vector()?.iter().cloned().collect())
+    }
+}
+
+pub struct FromList;
+
+impl Variable for FromList {
+    fn eval(&self, _env: &Environment, args: Vec<Value>) -> Result<Value, Error> {
+        if args.len() != 1 {
+            return Err(Error::VarEvalArgNumError { expected: Arity::Exactly(1), actual: args.len() });
+        }
+
+        let list = args.into_iter().next().unwrap_or_default().to_list().ok_or(Error::VarEvalError("not a list".to_string()))?;
+
+        Ok(Value::Vector(Rc::new(list.into_iter().collect())))
+    }
+}
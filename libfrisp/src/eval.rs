@@ -0,0 +1,684 @@
+use std::rc::Rc;
+
+use crate::{ast::AstNode, env::{map, vector, Env, Environment}, params::Params, pattern::Pattern, value::{ConstVal, Lambda, Native, Value, Variable}, Error};
+
+/// Default for the maximum number of frames on the evaluation stack.
+pub const DEFAULT_MAX_DEPTH: usize = 1_000_000;
+
+/// Pending work that is resumed once the value of a sub-form is known.
+enum Frame {
+    /// Waiting for the test of an `if`.
+    If { conseq: AstNode, alt: AstNode, env: Environment },
+    /// Waiting for the test of the `cond` clause at `next`.
+    Cond { forms: Rc<[AstNode]>, next: usize, env: Environment },
+    /// Waiting for the key of a `case`.
+    Case { forms: Rc<[AstNode]>, env: Environment },
+    /// Waiting for the value that a `match` dispatches on.
+    Match { forms: Rc<[AstNode]>, env: Environment },
+    /// Waiting for the guard of the `match` clause at `next`, whose pattern matched `value`.
+    /// The bindings of the pattern are in `scope`.
+    MatchGuard { forms: Rc<[AstNode]>, next: usize, value: Value, scope: Environment, env: Environment },
+    /// Waiting for the test of a `when`, or of an `unless` if `negate` is set.
+    When { forms: Rc<[AstNode]>, negate: bool, env: Environment },
+    /// Waiting for the operand at `next` of an `and`, or of an `or` if `is_or` is set.
+    AndOr { forms: Rc<[AstNode]>, next: usize, is_or: bool, env: Environment },
+    /// Waiting for the default value of the optional parameter at `next`.
+    /// The body is evaluated once all defaults are bound.
+    Defaults { defaults: Vec<(String, AstNode)>, next: usize, body: Rc<[AstNode]>, env: Environment },
+    /// Waiting for the value of a `define`.
+    Define { pattern: Pattern, env: Environment },
+    /// Waiting for the value of a `set!`.
+    Set { symbol: String, env: Environment },
+    /// Waiting for an argument of an in-place update like `hash-set!`, which changes the value
+    /// of `symbol` with `update` once all arguments are known.
+    Update { update: Update, symbol: String, forms: Rc<[AstNode]>, args: Vec<Value>, env: Environment },
+    /// Waiting for an element of a map or vector literal, which is created with `collect` once all elements are known.
+    Collect { items: Rc<[AstNode]>, values: Vec<Value>, collect: fn(Vec<Value>) -> Result<Value, Error>, env: Environment },
+    /// Waiting for a form of a body; the forms from `next` on are still to be evaluated.
+    Body { forms: Rc<[AstNode]>, next: usize, env: Environment },
+    /// Waiting for a form of a `try`.
+    Try { forms: Rc<[AstNode]>, next: usize, env: Environment },
+    /// Waiting for the procedure of a call whose head is not a symbol.
+    Callee { forms: Rc<[AstNode]>, env: Environment },
+    /// Waiting for an argument of a call.
+    Call { callee: Rc<dyn Variable>, forms: Rc<[AstNode]>, args: Vec<Value>, env: Environment },
+    /// Waiting for the value of the next binding of a `let`, `let*` or `letrec`.
+    Let(LetState),
+    /// Waiting for the value of a hole in a quasiquote template.
+    Quasiquote { template: AstNode, holes: Vec<AstNode>, values: Vec<Value>, env: Environment },
+    /// Waiting for a macro to produce the form that is evaluated in its place.
+    MacroExpansion { env: Environment },
+    /// Waiting for the script argument of `eval`.
+    #[cfg(feature = "eval")]
+    EvalScript { env: Environment },
+}
+
+/// Changes a value in place with the evaluated arguments of an update form like `hash-set!`.
+type Update = fn(&mut Value, Vec<Value>) -> Result<(), Error>;
+
+#[derive(Clone, Copy)]
+enum LetKind {
+    /// All values are evaluated in the outer scope before any is bound.
+    Let,
+    /// Each value is evaluated in a scope that contains the bindings before it.
+    LetStar,
+    /// All values are evaluated in the scope that contains all of the bindings.
+    LetRec,
+}
+
+/// Progress of a `let`, `let*` or `letrec`.
+struct LetState {
+    kind: LetKind,
+    bindings: Vec<(Pattern, AstNode)>,
+    /// Index of the binding whose value is evaluated.
+    next: usize,
+    /// Values of a `let`, which are only bound once all are evaluated.
+    values: Vec<Value>,
+    forms: Rc<[AstNode]>,
+    outer: Environment,
+    scope: Environment,
+}
+
+enum State {
+    Eval(AstNode, Environment),
+    Return(Value),
+}
+
+/// Evaluator that keeps its continuation in a heap allocated stack of frames,
+/// so that the depth of lisp recursion does not depend on the Rust call stack.
+pub struct Evaluator {
+    stack: Vec<Frame>,
+    max_depth: usize,
+}
+
+impl Evaluator {
+
+    pub fn new(max_depth: usize) -> Evaluator {
+        Evaluator { stack: Vec::new(), max_depth }
+    }
+
+    pub fn eval(&mut self, node: &AstNode, env: &Environment) -> Result<Value, Error> {
+        self.run(Ok(State::Eval(node.clone(), env.clone())))
+    }
+
+    /// Calls `lambda` with `args` and returns the value of its body.
+    pub fn call(&mut self, lambda: &Lambda, args: Vec<Value>) -> Result<Value, Error> {
+        let state = self.apply_lambda(lambda, args);
+        self.run(state)
+    }
+
+    fn run(&mut self, mut state: Result<State, Error>) -> Result<Value, Error> {
+        loop {
+            state = match state {
+                Ok(State::Eval(node, env)) => self.eval_node(node, env),
+                Ok(State::Return(value)) => match self.stack.pop() {
+                    Some(frame) => self.resume(frame, value),
+                    None => return Ok(value),
+                },
+                Err(e) => {
+                    if !self.unwind_to_try() {
+                        return Err(e);
+                    }
+                    Ok(State::Return(Value::Error(e.to_string())))
+                },
+            };
+        }
+    }
+
+    /// Drops all frames up to and including the innermost `try`, whose value then becomes the error.
+    /// Returns `false` if there is no `try` to catch the error.
+    fn unwind_to_try(&mut self) -> bool {
+        while let Some(frame) = self.stack.pop() {
+            if let Frame::Try { .. } = frame {
+                return true;
+            }
+        }
+        false
+    }
+
+    fn push(&mut self, frame: Frame) -> Result<(), Error> {
+        if self.stack.len() >= self.max_depth {
+            return Err(Error::RecursionLimitError { limit: self.max_depth });
+        }
+        self.stack.push(frame);
+        Ok(())
+    }
+
+    fn eval_node(&mut self, node: AstNode, env: Environment) -> Result<State, Error> {
+        match node {
+            AstNode::List(l) => {
+                #[cfg(feature = "log")]
+                println!("evaluating {:?}", l.first());
+
+                match l.first() {
+                    Some(AstNode::Symbol(s)) => {
+                        self.eval_list(env, s, &l)
+                    },
+                    Some(head) => {
+                        let head = head.clone();
+                        self.push(Frame::Callee { forms: l, env: env.clone() })?;
+                        Ok(State::Eval(head, env))
+                    },
+                    None => {
+                        Ok(State::Return(Value::Unit))
+                    },
+                }
+            },
+            AstNode::Dotted(..) => {
+                Err(Error::EvalError("cannot evaluate a dotted list".to_string()))
+            },
+            AstNode::Symbol(s) => {
+                let var = env.get_var(&s).ok_or_else(|| Error::EvalError(format!("symbol not found: {s:?}")))?;
+                let res = var.val().unwrap_or_else(|| Value::Native(Native::new(&s, var)));
+                #[cfg(feature = "log")]
+                println!("Symboling {s:?} to {res:?}");
+                Ok(State::Return(res))
+            },
+            AstNode::Value(v) => {
+                #[cfg(feature = "log")]
+                println!("Valuing {v:?}");
+                Ok(State::Return(v))
+            },
+            AstNode::Map(items) => {
+                self.continue_collect(items, Vec::new(), Value::map_of_entries, env)
+            },
+            AstNode::Vector(items) => {
+                self.continue_collect(items, Vec::new(), |values| Ok(Value::Vector(Rc::new(values))), env)
+            },
+        }
+    }
+
+    fn eval_list(&mut self, env: Environment, symbol: &str, l: &Rc<[AstNode]>) -> Result<State, Error> {
+        match symbol {
+            "if" => {
+                let test = l.get(1).ok_or(Error::EvalError("missing test".to_string()))?;
+                let conseq = l.get(2).ok_or(Error::EvalError("missing conseq".to_string()))?;
+                let alt = l.get(3).ok_or(Error::EvalError("missing alt".to_string()))?;
+
+                self.push(Frame::If { conseq: conseq.clone(), alt: alt.clone(), env: env.clone() })?;
+                Ok(State::Eval(test.clone(), env))
+            },
+            "cond" => {
+                self.continue_cond(l.clone(), 1, env)
+            },
+            "case" => {
+                let key = l.get(1).ok_or(Error::EvalError("missing key".to_string()))?;
+                self.push(Frame::Case { forms: l.clone(), env: env.clone() })?;
+                Ok(State::Eval(key.clone(), env))
+            },
+            "match" => {
+                let value = l.get(1).ok_or(Error::EvalError("missing value to match".to_string()))?;
+                self.push(Frame::Match { forms: l.clone(), env: env.clone() })?;
+                Ok(State::Eval(value.clone(), env))
+            },
+            "when" | "unless" => {
+                let test = l.get(1).ok_or(Error::EvalError("missing test".to_string()))?;
+                self.push(Frame::When { forms: l.clone(), negate: symbol == "unless", env: env.clone() })?;
+                Ok(State::Eval(test.clone(), env))
+            },
+            "and" | "or" => {
+                self.continue_and_or(l.clone(), 1, symbol == "or", env)
+            },
+            "define" => {
+                let pattern = l.get(1).ok_or(Error::EvalError("no symbol for define".to_string()))?;
+                let pattern = Pattern::parse(pattern)?;
+                let val = l.get(2).ok_or(Error::EvalError("no value for define".to_string()))?;
+
+                self.push(Frame::Define { pattern, env: env.clone() })?;
+                Ok(State::Eval(val.clone(), env))
+            },
+            "set!" => {
+                let symbol = l.get(1).ok_or(Error::EvalError("no symbol for set!".to_string()))?;
+                let symbol = symbol.to_owned().try_to_symbol().map_err(|n| Error::EvalError(format!("not a symbol: {n:?}")))?;
+                let val = l.get(2).ok_or(Error::EvalError("no value for set!".to_string()))?;
+
+                self.push(Frame::Set { symbol, env: env.clone() })?;
+                Ok(State::Eval(val.clone(), env))
+            },
+            "hash-set!" => self.begin_update(symbol, map::set_in_place, 2, l.clone(), env),
+            "hash-remove!" => self.begin_update(symbol, map::remove_in_place, 1, l.clone(), env),
+            "vector-set!" => self.begin_update(symbol, vector::set_in_place, 2, l.clone(), env),
+            "vector-push!" => self.begin_update(symbol, vector::push_in_place, 1, l.clone(), env),
+            "lambda" => {
+                let lambda = Self::make_lambda("lambda", &l[1..], env)?;
+                Ok(State::Return(Value::Lambda(Rc::new(lambda))))
+            },
+            "defmacro" => {
+                let symbol = l.get(1).ok_or(Error::EvalError("no symbol for defmacro".to_string()))?;
+                let symbol = symbol.to_owned().try_to_symbol().map_err(|n| Error::EvalError(format!("not a symbol: {n:?}")))?;
+
+                let mut env = env;
+                let lambda = Self::make_lambda("defmacro", &l[2..], env.clone())?;
+                #[cfg(feature = "log")]
+                println!("defined macro {symbol}");
+                env.insert_value(symbol, Value::Macro(Rc::new(lambda)));
+
+                Ok(State::Return(Value::Unit))
+            },
+            "progn" => {
+                self.begin_body(l.clone(), 1, env)
+            },
+            "begin" => {
+                if l.len() == 1 {
+                    return Ok(State::Return(Value::Unit));
+                }
+                self.begin_body(l.clone(), 1, env)
+            },
+            "quote" => {
+                let v: Value = l[1..].iter().map(|v| v.quote()).collect();
+                Ok(State::Return(v.unwrap_single_value_list()))
+            },
+            "let" => self.begin_let(LetKind::Let, l.clone(), env),
+            "let*" => self.begin_let(LetKind::LetStar, l.clone(), env),
+            "letrec" => self.begin_let(LetKind::LetRec, l.clone(), env),
+            "quasiquote" => {
+                let template = l.get(1).ok_or(Error::EvalError("no template for quasiquote".to_string()))?;
+
+                let mut holes = Vec::new();
+                template.quasiquote_holes(1, &mut holes);
+
+                self.continue_quasiquote(template.clone(), holes, Vec::new(), env)
+            },
+            "try" => {
+                let first = l.get(1).ok_or(Error::VarEvalError("no value".to_string()))?;
+                self.push(Frame::Try { forms: l.clone(), next: 2, env: env.clone() })?;
+                Ok(State::Eval(first.clone(), env))
+            },
+            #[cfg(feature = "eval")]
+            "eval" => {
+                let script = l.get(1).ok_or(Error::EvalError("no args for eval".to_string()))?;
+                self.push(Frame::EvalScript { env: env.clone() })?;
+                Ok(State::Eval(script.clone(), env))
+            },
+
+            #[cfg(feature = "include")]
+            "include" => {
+                let path = l.get(1).ok_or(Error::EvalError("no args for include".to_string()))?;
+                let path_val = path.to_owned().try_to_value().map_err(|v| Error::EvalError(format!("{v:?} is not a value")))?;
+                let path_str = path_val.as_str().ok_or_else(|| Error::EvalError(format!("{path_val:?} is not a string")))?;
+
+                crate::eval_file_with_env(path_str, &mut env.clone()).map(State::Return)
+            },
+
+            s => {
+                let var = env.get_var(s).ok_or_else(|| Error::EvalError(format!("proc not found: {s}")))?;
+
+                if let Some(Value::Macro(m)) = var.val() {
+                    let args = l[1..].iter().map(|n| n.quote()).collect();
+                    self.push(Frame::MacroExpansion { env })?;
+                    return self.apply_lambda(&m, args);
+                }
+
+                self.begin_call(var, l.clone(), Vec::new(), env)
+            }
+
+        }
+    }
+
+    fn resume(&mut self, frame: Frame, value: Value) -> Result<State, Error> {
+        match frame {
+            Frame::If { conseq, alt, env } => {
+                if value.is_truthy() {
+                    Ok(State::Eval(conseq, env))
+                } else {
+                    Ok(State::Eval(alt, env))
+                }
+            },
+            Frame::Cond { forms, next, env } => {
+                if !value.is_truthy() {
+                    return self.continue_cond(forms, next + 1, env);
+                }
+
+                let clause = Self::clause(&forms[next])?;
+                if clause.len() == 1 {
+                    return Ok(State::Return(value));
+                }
+                self.begin_body(clause, 1, env)
+            },
+            Frame::Match { forms, env } => {
+                self.continue_match(forms, 2, value, env)
+            },
+            Frame::MatchGuard { forms, next, value: matched, scope, env } => {
+                if value.is_truthy() {
+                    return self.begin_body(Self::clause(&forms[next])?, 3, scope);
+                }
+                self.continue_match(forms, next + 1, matched, env)
+            },
+            Frame::Case { forms, env } => {
+                for clause in &forms[2..] {
+                    let clause = Self::clause(clause)?;
+                    let matches = match &clause[0] {
+                        AstNode::Symbol(s) if s == "else" => true,
+                        AstNode::List(keys) => keys.iter().any(|key| key.quote() == value),
+                        key => key.quote() == value,
+                    };
+
+                    if matches {
+                        return self.begin_body(clause, 1, env);
+                    }
+                }
+
+                Ok(State::Return(Value::Unit))
+            },
+            Frame::When { forms, negate, env } => {
+                if value.is_truthy() == negate || forms.len() == 2 {
+                    return Ok(State::Return(Value::Unit));
+                }
+                self.begin_body(forms, 2, env)
+            },
+            Frame::AndOr { forms, next, is_or, env } => {
+                if value.is_truthy() == is_or {
+                    return Ok(State::Return(value));
+                }
+                self.continue_and_or(forms, next + 1, is_or, env)
+            },
+            Frame::Defaults { defaults, next, body, mut env } => {
+                env.insert_value(&defaults[next].0, value);
+                self.continue_defaults(defaults, next + 1, body, env)
+            },
+            Frame::Define { pattern, mut env } => {
+                #[cfg(feature = "log")]
+                println!("defined {pattern} to be {value:?}");
+                pattern.bind(&mut env, value)?;
+                Ok(State::Return(Value::Unit))
+            },
+            Frame::Set { symbol, mut env } => {
+                #[cfg(feature = "log")]
+                println!("set {symbol} to {value:?}");
+                if !env.set_value(&symbol, value) {
+                    return Err(Error::EvalError(format!("cannot set! undefined variable: {symbol}")));
+                }
+                Ok(State::Return(Value::Unit))
+            },
+            Frame::Update { update, symbol, forms, mut args, env } => {
+                args.push(value);
+                self.continue_update(update, symbol, forms, args, env)
+            },
+            Frame::Collect { items, mut values, collect, env } => {
+                values.push(value);
+                self.continue_collect(items, values, collect, env)
+            },
+            Frame::Body { forms, next, env } => {
+                self.begin_body(forms, next, env)
+            },
+            Frame::Try { forms, next, env } => {
+                match forms.get(next) {
+                    Some(form) => {
+                        let form = form.clone();
+                        self.push(Frame::Try { forms, next: next + 1, env: env.clone() })?;
+                        Ok(State::Eval(form, env))
+                    },
+                    None => Ok(State::Return(value)),
+                }
+            },
+            Frame::Callee { forms, env } => {
+                self.begin_call(Rc::new(ConstVal::from(value)), forms, Vec::new(), env)
+            },
+            Frame::Call { callee, forms, mut args, env } => {
+                args.push(value);
+                self.begin_call(callee, forms, args, env)
+            },
+            Frame::Let(mut state) => {
+                let pattern = &state.bindings[state.next].0;
+                match state.kind {
+                    LetKind::Let => state.values.push(value),
+                    LetKind::LetStar => {
+                        state.scope = state.scope.sub_env();
+                        pattern.bind(&mut state.scope, value)?;
+                    },
+                    LetKind::LetRec => pattern.bind(&mut state.scope, value)?,
+                }
+                state.next += 1;
+                self.continue_let(state)
+            },
+            Frame::Quasiquote { template, holes, mut values, env } => {
+                values.push(value);
+                self.continue_quasiquote(template, holes, values, env)
+            },
+            Frame::MacroExpansion { env } => {
+                let expansion = AstNode::from(value);
+                #[cfg(feature = "log")]
+                println!("expanded macro to {expansion:?}");
+                Ok(State::Eval(expansion, env))
+            },
+            #[cfg(feature = "eval")]
+            Frame::EvalScript { mut env } => {
+                let script_str = value.as_str().ok_or_else(|| Error::EvalError(format!("{value:?} is not a string")))?;
+
+                let res = crate::run_with_env(script_str, &mut env)?;
+
+                #[cfg(feature = "log")]
+                println!("evaluated {script_str:?} to {res:?}");
+                Ok(State::Return(res))
+            },
+        }
+    }
+
+    /// Continues evaluating `forms` from `start` on. The last form is evaluated
+    /// without leaving a frame behind, which makes it a proper tail call.
+    fn begin_body(&mut self, forms: Rc<[AstNode]>, start: usize, env: Environment) -> Result<State, Error> {
+        let form = forms.get(start).ok_or(Error::VarEvalError("no value".to_string()))?.clone();
+
+        if start + 1 < forms.len() {
+            self.push(Frame::Body { forms, next: start + 1, env: env.clone() })?;
+        }
+
+        Ok(State::Eval(form, env))
+    }
+
+    /// Evaluates the next argument of a call, or applies the procedure once all arguments are known.
+    fn begin_call(&mut self, callee: Rc<dyn Variable>, forms: Rc<[AstNode]>, args: Vec<Value>, env: Environment) -> Result<State, Error> {
+        if let Some(arg) = forms.get(args.len() + 1) {
+            let arg = arg.clone();
+            self.push(Frame::Call { callee, forms, args, env: env.clone() })?;
+            return Ok(State::Eval(arg, env));
+        }
+
+        self.apply(&env, callee, args)
+    }
+
+    /// Starts an update form `(name symbol args...)` that takes `arity` arguments.
+    fn begin_update(&mut self, name: &str, update: Update, arity: usize, forms: Rc<[AstNode]>, env: Environment) -> Result<State, Error> {
+        if forms.len() != arity + 2 {
+            return Err(Error::EvalError(format!("wrong number of forms for {name}")));
+        }
+        let symbol = forms[1].to_owned().try_to_symbol().map_err(|n| Error::EvalError(format!("not a symbol: {n:?}")))?;
+
+        self.continue_update(update, symbol, forms, Vec::new(), env)
+    }
+
+    /// Evaluates the next argument of an update form, or changes the value of `symbol` once all arguments are known.
+    fn continue_update(&mut self, update: Update, symbol: String, forms: Rc<[AstNode]>, args: Vec<Value>, env: Environment) -> Result<State, Error> {
+        if let Some(arg) = forms.get(args.len() + 2) {
+            let arg = arg.clone();
+            self.push(Frame::Update { update, symbol, forms, args, env: env.clone() })?;
+            return Ok(State::Eval(arg, env));
+        }
+
+        env.update_value(&symbol, |value| update(value, args))?;
+        Ok(State::Return(Value::Unit))
+    }
+
+    /// Evaluates the next element of a literal, or creates its value with `collect` once all elements are known.
+    fn continue_collect(&mut self, items: Rc<[AstNode]>, values: Vec<Value>, collect: fn(Vec<Value>) -> Result<Value, Error>, env: Environment) -> Result<State, Error> {
+        if let Some(item) = items.get(values.len()) {
+            let item = item.clone();
+            self.push(Frame::Collect { items, values, collect, env: env.clone() })?;
+            return Ok(State::Eval(item, env));
+        }
+
+        collect(values).map(State::Return)
+    }
+
+    /// Evaluates the test of the `cond` clause at `next`. Clauses starting with `else` are taken unconditionally.
+    fn continue_cond(&mut self, forms: Rc<[AstNode]>, next: usize, env: Environment) -> Result<State, Error> {
+        let Some(clause) = forms.get(next) else {
+            return Ok(State::Return(Value::Unit));
+        };
+
+        let clause = Self::clause(clause)?;
+        match &clause[0] {
+            AstNode::Symbol(s) if s == "else" => self.begin_body(clause, 1, env),
+            test => {
+                let test = test.clone();
+                self.push(Frame::Cond { forms, next, env: env.clone() })?;
+                Ok(State::Eval(test, env))
+            },
+        }
+    }
+
+    /// Tries the `match` clauses from `next` on until the pattern of one matches `value`, then evaluates
+    /// its body with the bindings of the pattern. A clause `(pattern :when guard body...)` is only
+    /// taken if `guard` is true.
+    fn continue_match(&mut self, forms: Rc<[AstNode]>, mut next: usize, value: Value, env: Environment) -> Result<State, Error> {
+        while let Some(clause) = forms.get(next) {
+            let clause = Self::clause(clause)?;
+            let pattern = Pattern::parse(&clause[0])?;
+
+            let mut bindings = Vec::new();
+            if pattern.destructure(&value, &mut bindings) {
+                let mut scope = env.sub_env();
+                for (name, value) in bindings {
+                    scope.insert_var(name, ConstVal::from(value));
+                }
+
+                return match &clause[1..] {
+                    [AstNode::Value(Value::Keyword(k)), guard, ..] if k == "when" => {
+                        let guard = guard.clone();
+                        self.push(Frame::MatchGuard { forms, next, value, scope: scope.clone(), env })?;
+                        Ok(State::Eval(guard, scope))
+                    },
+                    _ => self.begin_body(clause, 1, scope),
+                };
+            }
+
+            next += 1;
+        }
+
+        Err(Error::EvalError(format!("no pattern matches {value}")))
+    }
+
+    /// Evaluates the operand of an `and` or `or` at `next`. The last operand is in tail position,
+    /// so its value is the result if no operand before it decided the outcome.
+    fn continue_and_or(&mut self, forms: Rc<[AstNode]>, next: usize, is_or: bool, env: Environment) -> Result<State, Error> {
+        let Some(operand) = forms.get(next) else {
+            return Ok(State::Return(Value::bool(!is_or)));
+        };
+
+        let operand = operand.clone();
+        if next + 1 < forms.len() {
+            self.push(Frame::AndOr { forms, next, is_or, env: env.clone() })?;
+        }
+        Ok(State::Eval(operand, env))
+    }
+
+    /// Returns the forms of a `cond` or `case` clause.
+    fn clause(clause: &AstNode) -> Result<Rc<[AstNode]>, Error> {
+        match clause {
+            AstNode::List(clause) if !clause.is_empty() => Ok(clause.clone()),
+            c => Err(Error::EvalError(format!("invalid clause: {c:?}"))),
+        }
+    }
+
+    fn begin_let(&mut self, kind: LetKind, forms: Rc<[AstNode]>, env: Environment) -> Result<State, Error> {
+        let bindings = forms.get(1).ok_or(Error::EvalError("no bindings for let".to_string()))?;
+        let bindings = bindings.to_owned().try_to_list().map_err(|n| Error::EvalError(format!("not a list: {n:?}")))?;
+
+        let bindings: Result<Vec<_>, Error> = bindings.into_iter()
+            .map(|binding| match binding.try_to_list().as_deref() {
+                Ok([pattern, value]) => Ok((Pattern::parse(pattern)?, value.to_owned())),
+                Ok(b) => Err(Error::EvalError(format!("invalid binding: {b:?}"))),
+                Err(n) => Err(Error::EvalError(format!("invalid binding: {n:?}"))),
+            })
+            .collect();
+
+        let scope = env.sub_env();
+        self.continue_let(LetState { kind, bindings: bindings?, next: 0, values: Vec::new(), forms, outer: env, scope })
+    }
+
+    /// Evaluates the value of the next binding, or the body once all bindings are made.
+    fn continue_let(&mut self, mut state: LetState) -> Result<State, Error> {
+        if let Some((_, value)) = state.bindings.get(state.next) {
+            let value = value.clone();
+            let env = match state.kind {
+                LetKind::Let => state.outer.clone(),
+                LetKind::LetStar | LetKind::LetRec => state.scope.clone(),
+            };
+            self.push(Frame::Let(state))?;
+            return Ok(State::Eval(value, env));
+        }
+
+        if let LetKind::Let = state.kind {
+            for ((pattern, _), value) in state.bindings.iter().zip(state.values) {
+                pattern.bind(&mut state.scope, value)?;
+            }
+        }
+
+        self.begin_body(state.forms, 2, state.scope)
+    }
+
+    fn apply_lambda(&mut self, lambda: &Lambda, args: Vec<Value>) -> Result<State, Error> {
+        let (local_env, defaults) = lambda.bind_args(args)?;
+        self.continue_defaults(defaults, 0, lambda.body(), local_env)
+    }
+
+    /// Evaluates the default value of the next missing optional parameter, or the body once all are bound.
+    fn continue_defaults(&mut self, defaults: Vec<(String, AstNode)>, next: usize, body: Rc<[AstNode]>, env: Environment) -> Result<State, Error> {
+        if let Some((_, default)) = defaults.get(next) {
+            let default = default.clone();
+            self.push(Frame::Defaults { defaults, next, body, env: env.clone() })?;
+            return Ok(State::Eval(default, env));
+        }
+
+        self.begin_body(body, 0, env)
+    }
+
+    /// Evaluates the next hole of a quasiquote template, or fills in the template once all holes are evaluated.
+    fn continue_quasiquote(&mut self, template: AstNode, holes: Vec<AstNode>, values: Vec<Value>, env: Environment) -> Result<State, Error> {
+        if let Some(hole) = holes.get(values.len()) {
+            let hole = hole.clone();
+            self.push(Frame::Quasiquote { template, holes, values, env: env.clone() })?;
+            return Ok(State::Eval(hole, env));
+        }
+
+        template.quasiquote(1, &mut values.into_iter()).map(State::Return)
+    }
+
+    /// Calls `var` with `args`. Lambda bodies are continued on the evaluation stack
+    /// instead of being evaluated recursively.
+    fn apply(&mut self, env: &Environment, mut var: Rc<dyn Variable>, args: Vec<Value>) -> Result<State, Error> {
+        loop {
+            match var.val() {
+                Some(Value::Lambda(lambda)) => {
+                    return self.apply_lambda(&lambda, args);
+                },
+                Some(Value::Macro(_)) => {
+                    return Err(Error::EvalError("a macro cannot be applied to evaluated arguments".to_string()));
+                },
+                Some(Value::Native(native)) => {
+                    var = native.var();
+                },
+                Some(Value::SymbolRef(sym)) => {
+                    #[cfg(feature = "log")]
+                    println!("getting symbol ref {sym}");
+                    var = env.resolve_var(&sym)?;
+                },
+                _ => {
+                    let value = var.eval(env, args);
+                    #[cfg(feature = "log")]
+                    println!("evaluated to {value:?}");
+                    return value.map(State::Return);
+                },
+            }
+        }
+    }
+
+    /// Creates a lambda from the parameter list and body forms in `l`.
+    fn make_lambda(form: &str, l: &[AstNode], env: Environment) -> Result<Lambda, Error> {
+        let params = l.first().ok_or_else(|| Error::EvalError(format!("no args for {form}")))?;
+        let body: Vec<_> = l[1..].to_vec();
+
+        Ok(Lambda::new(Params::parse(params)?, body, env))
+    }
+
+}
@@ -1,16 +1,14 @@
 use std::{fs, path::PathBuf, str::FromStr};
 
-use crate::{env::{Env, Environment}, value::{Value, Variable}, Error};
+use crate::{env::{Env, Environment}, value::{Value, Variable}, Arity, Error};
 
 struct Assert;
 
 impl Variable for Assert {
     fn eval(&self, _env: &Environment, args: Vec<Value>) -> Result<Value, Error> {
         for arg in &args {
-            match arg {
-                Value::Integer(1) => {}
-                Value::Integer(0) => return Err(Error::VarEvalError(format!("assertion failed"))),
-                v => return Err(Error::VarEvalError(format!("assertion failed: {v}"))),
+            if !arg.is_truthy() {
+                return Err(Error::VarEvalError(format!("assertion failed: {arg}")));
             }
         }
 
@@ -22,10 +20,10 @@ struct AssertEq;
 impl Variable for AssertEq {
     fn eval(&self, _env: &Environment, args: Vec<Value>) -> Result<Value, Error> {
         if args.len() != 2 {
-            return Err(Error::VarEvalArgNumError { expected: 2, actual: args.len() });
+            return Err(Error::VarEvalArgNumError { expected: Arity::Exactly(2), actual: args.len() });
         }
 
-        if &args[0] != &args[1] {
+        if args[0] != args[1] {
             Err(Error::VarEvalError(format!("assertion failed: {:?} != {:?}", &args[0], &args[1])))
         } else {
             Ok(Value::Unit)
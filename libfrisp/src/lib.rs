@@ -6,9 +6,16 @@ use token::TokenStream;
 use value::Value;
 
 pub mod value;
+pub mod list;
+pub mod bigint;
+pub mod ratio;
+pub mod number;
 pub mod token;
 pub mod ast;
 pub mod env;
+pub mod eval;
+pub mod params;
+pub mod pattern;
 
 #[cfg(test)]
 mod frisp_test;
@@ -20,9 +27,15 @@ pub enum Error {
     EvalError(String),
     VarEvalError(String),
     VarEvalArgNumError {
-        expected: usize,
+        expected: Arity,
         actual: usize,
     },
+    RecursionLimitError {
+        limit: usize,
+    },
+    DivisionByZero,
+    /// The result of an arithmetic operation does not fit into the type it has to be represented in.
+    Overflow(String),
 }
 
 impl Display for Error {
@@ -33,6 +46,39 @@ impl Display for Error {
             Error::EvalError(e) => write!(f, "Evaluation error: {e}"),
             Error::VarEvalError(e) => write!(f, "{e}"),
             Error::VarEvalArgNumError { expected, actual } => write!(f, "Invalid number of arguments. Expected {expected} but got {actual}"),
+            Error::RecursionLimitError { limit } => write!(f, "Recursion limit exceeded: more than {limit} frames on the evaluation stack"),
+            Error::DivisionByZero => write!(f, "Division by zero"),
+            Error::Overflow(e) => write!(f, "Arithmetic overflow: {e}"),
+        }
+    }
+}
+
+/// Number of arguments a procedure accepts.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Arity {
+    Exactly(usize),
+    AtLeast(usize),
+    Between(usize, usize),
+}
+
+impl Arity {
+
+    pub fn accepts(&self, count: usize) -> bool {
+        match *self {
+            Arity::Exactly(n) => count == n,
+            Arity::AtLeast(min) => count >= min,
+            Arity::Between(min, max) => (min..=max).contains(&count),
+        }
+    }
+
+}
+
+impl Display for Arity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Arity::Exactly(n) => write!(f, "{n}"),
+            Arity::AtLeast(min) => write!(f, "at least {min}"),
+            Arity::Between(min, max) => write!(f, "between {min} and {max}"),
         }
     }
 }
@@ -70,7 +116,9 @@ pub fn eval_file_with_env<P: AsRef<Path>>(path: P, env: &mut Environment) -> Res
 
 #[cfg(test)]
 mod test {
-    use crate::{env::Environment, run_with_env, value::Value};
+    use std::rc::Rc;
+
+    use crate::{env::{Env, Environment}, run_with_env, value::{ConstVal, Value}, Error};
 
 
     #[test]
@@ -79,7 +127,7 @@ mod test {
         let fib_code = include_str!("../../res/fib.lisp");
 
         assert_eq!(Value::Unit, run_with_env(fib_code, &mut env).unwrap());
-        assert_eq!(Value::int(55isize), run_with_env(&format!("(fib 10)"), &mut env).unwrap());
+        assert_eq!(Value::int(55isize), run_with_env("(fib 10)", &mut env).unwrap());
     }
 
     #[test]
@@ -88,17 +136,80 @@ mod test {
         let gcd_code = include_str!("../../res/gcd.lisp");
 
         assert_eq!(Value::Unit, run_with_env(gcd_code, &mut env).unwrap());
-        assert_eq!(Value::int(3isize), run_with_env(&format!("(gcd 1098 1173)"), &mut env).unwrap());
+        assert_eq!(Value::int(3isize), run_with_env("(gcd 1098 1173)", &mut env).unwrap());
+    }
+
+    #[test]
+    fn test_gcd_builtins() {
+        let mut env = Environment::with_default_content();
+
+        assert_eq!(Value::int(3isize), run_with_env("(gcd 1098 1173)", &mut env).unwrap());
+        assert_eq!(Value::int(429_318isize), run_with_env("(lcm 1098 1173)", &mut env).unwrap());
     }
 
     #[test]
     fn test_include() {
         let mut env = Environment::with_default_content();
 
-        assert_eq!(Value::Unit, run_with_env(&format!("(include \"../res/include_test.lisp\")"), &mut env).unwrap());
+        assert_eq!(Value::Unit, run_with_env("(include \"../res/include_test.lisp\")", &mut env).unwrap());
         assert_eq!(Value::Integer(2), run_with_env("a", &mut env).unwrap());
         assert_eq!(Value::Integer(3), run_with_env("b", &mut env).unwrap());
         assert_eq!(Value::Integer(5), run_with_env("c", &mut env).unwrap());
     }
 
+    #[test]
+    fn test_local_lambdas_free_their_scope() {
+        let mut env = Environment::with_default_content();
+        let probe = Rc::new(vec![Value::Integer(1)]);
+        env.insert_var("probe", ConstVal::from(Value::Vector(probe.clone())));
+
+        let code = "(define work (lambda (v)
+            (define copy v)
+            (define helper (lambda (n) (if (== n 0) copy (helper (- n 1)))))
+            (helper 3)))";
+        assert_eq!(Value::Unit, run_with_env(code, &mut env).unwrap());
+        assert_eq!(Value::Unit, run_with_env(include_str!("../../res/list_util.lisp"), &mut env).unwrap());
+
+        let before = Rc::strong_count(&probe);
+        for _ in 0..100 {
+            assert_eq!(Value::Vector(probe.clone()), run_with_env("(work probe)", &mut env).unwrap());
+            assert_eq!(Value::Integer(1), run_with_env("(index-of probe (list 0 probe))", &mut env).unwrap());
+        }
+        assert_eq!(before, Rc::strong_count(&probe));
+
+        let code = "(define make-counter (lambda (n)
+            (define next (lambda () (set! n (+ n 1)) n))
+            next))";
+        assert_eq!(Value::Unit, run_with_env(code, &mut env).unwrap());
+        assert_eq!(Value::Unit, run_with_env("(define counter (make-counter 5))", &mut env).unwrap());
+        assert_eq!(Value::Integer(6), run_with_env("(counter)", &mut env).unwrap());
+        assert_eq!(Value::Integer(7), run_with_env("(counter)", &mut env).unwrap());
+    }
+
+    #[test]
+    fn test_recursion_limit() {
+        let mut env = Environment::with_default_content();
+        env.set_max_depth(100);
+
+        let countdown_code = "(define countdown (lambda (n) (if (== n 0) 0 (+ 1 (countdown (- n 1))))))";
+        assert_eq!(Value::Unit, run_with_env(countdown_code, &mut env).unwrap());
+        assert_eq!(Value::int(10isize), run_with_env("(countdown 10)", &mut env).unwrap());
+
+        let err = run_with_env("(countdown 1000)", &mut env).unwrap_err();
+        assert!(matches!(err, Error::RecursionLimitError { limit: 100 }));
+
+        let caught = run_with_env("(try (countdown 1000))", &mut env).unwrap();
+        assert!(matches!(caught, Value::Error(_)));
+    }
+
+    #[test]
+    fn test_arithmetic_errors() {
+        let mut env = Environment::with_default_content();
+
+        assert!(matches!(run_with_env("(/ 1 0)", &mut env), Err(Error::DivisionByZero)));
+        assert!(matches!(run_with_env("(mod 1 0)", &mut env), Err(Error::DivisionByZero)));
+        assert!(matches!(run_with_env("(*% 9223372036854775808 2)", &mut env), Err(Error::Overflow(_))));
+        assert_eq!(Value::Integer(isize::MIN), run_with_env("(+% 9223372036854775807 1)", &mut env).unwrap());
+    }
+
 }
\ No newline at end of file
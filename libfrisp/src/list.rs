@@ -0,0 +1,179 @@
+use std::{fmt::Debug, rc::Rc};
+
+use crate::value::Value;
+
+/// Persistent singly linked list of values.
+///
+/// Lists share their tails, so [`List::cons`] and [`List::rest`] take constant time
+/// and never copy elements. Rust code can build lists with [`FromIterator`] or `From<Vec<Value>>`
+/// and walk them with [`List::iter`] or [`IntoIterator`].
+#[derive(Clone, Default)]
+pub struct List(Option<Rc<Node>>);
+
+struct Node {
+    first: Value,
+    rest: List,
+}
+
+impl List {
+
+    pub fn new() -> List {
+        List(None)
+    }
+
+    /// Returns a list with `first` in front of this list.
+    pub fn cons(&self, first: Value) -> List {
+        List(Some(Rc::new(Node { first, rest: self.clone() })))
+    }
+
+    pub fn first(&self) -> Option<&Value> {
+        self.0.as_ref().map(|node| &node.first)
+    }
+
+    /// Returns the list without its first element, or `None` if it is empty.
+    pub fn rest(&self) -> Option<List> {
+        self.0.as_ref().map(|node| node.rest.clone())
+    }
+
+    pub fn split_first(&self) -> Option<(&Value, List)> {
+        self.0.as_ref().map(|node| (&node.first, node.rest.clone()))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_none()
+    }
+
+    /// Counts the elements, which takes linear time.
+    pub fn len(&self) -> usize {
+        self.iter().count()
+    }
+
+    pub fn iter(&self) -> Iter<'_> {
+        Iter(self.0.as_deref())
+    }
+
+}
+
+impl From<Vec<Value>> for List {
+    fn from(values: Vec<Value>) -> Self {
+        values.into_iter().rev().fold(List::new(), |list, value| list.cons(value))
+    }
+}
+
+impl FromIterator<Value> for List {
+    fn from_iter<T: IntoIterator<Item = Value>>(iter: T) -> Self {
+        List::from(iter.into_iter().collect::<Vec<_>>())
+    }
+}
+
+impl PartialEq for List {
+    fn eq(&self, other: &Self) -> bool {
+        let mut a = self.iter();
+        let mut b = other.iter();
+        loop {
+            match (a.0, b.0) {
+                (Some(x), Some(y)) if std::ptr::eq(x, y) => return true,
+                _ => {},
+            }
+            match (a.next(), b.next()) {
+                (Some(x), Some(y)) if x == y => continue,
+                (None, None) => return true,
+                _ => return false,
+            }
+        }
+    }
+}
+
+impl Debug for List {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+/// Drops the nodes one after another, so that dropping a long list does not overflow the stack.
+impl Drop for List {
+    fn drop(&mut self) {
+        let mut next = self.0.take();
+        while let Some(node) = next {
+            next = match Rc::try_unwrap(node) {
+                Ok(mut node) => node.rest.0.take(),
+                Err(_) => None,
+            };
+        }
+    }
+}
+
+pub struct Iter<'a>(Option<&'a Node>);
+
+impl<'a> Iterator for Iter<'a> {
+    type Item = &'a Value;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.0?;
+        self.0 = node.rest.0.as_deref();
+        Some(&node.first)
+    }
+}
+
+impl<'a> IntoIterator for &'a List {
+    type Item = &'a Value;
+    type IntoIter = Iter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// Iterator over the elements of an owned list. Elements are moved out of nodes
+/// that are not shared with other lists, and cloned otherwise.
+pub struct IntoIter(List);
+
+impl Iterator for IntoIter {
+    type Item = Value;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.0.0.take()?;
+        match Rc::try_unwrap(node) {
+            Ok(mut node) => {
+                self.0 = std::mem::take(&mut node.rest);
+                Some(std::mem::take(&mut node.first))
+            },
+            Err(node) => {
+                self.0 = node.rest.clone();
+                Some(node.first.clone())
+            },
+        }
+    }
+}
+
+impl IntoIterator for List {
+    type Item = Value;
+    type IntoIter = IntoIter;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn collect_and_iterate() {
+        let list: List = (1..=3isize).map(Value::int).collect();
+
+        assert_eq!(3, list.len());
+        assert_eq!(Some(&Value::int(1isize)), list.first());
+        assert_eq!(vec![Value::int(2isize), Value::int(3isize)], list.rest().unwrap().into_iter().collect::<Vec<_>>());
+        assert_eq!(list, List::from(vec![Value::int(1isize), Value::int(2isize), Value::int(3isize)]));
+    }
+
+    #[test]
+    fn drop_long_list() {
+        let list: List = (0..1_000_000isize).map(Value::int).collect();
+        let shared = list.rest().unwrap();
+        drop(list);
+        assert_eq!(999_999, shared.len());
+    }
+}
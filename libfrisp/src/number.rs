@@ -0,0 +1,165 @@
+//! Grammar of numeric literals.
+//!
+//! A literal has an optional sign followed by one of
+//! - `0x`, `0o` or `0b` and hexadecimal, octal or binary digits, which is an integer,
+//! - decimal digits, which is an integer,
+//! - decimal digits, `/` and decimal digits, which is a ratio like `1/3`,
+//! - decimal digits with a fraction `.5` and/or an exponent `e-3`, which is a float,
+//! - `inf.0` or `nan.0`, which is an infinite or NaN float and requires the sign.
+//!
+//! Digits can be grouped with single `_` between them, like `1_000_000`. Anything else,
+//! including `inf` or `nan`, is not a number and is read as a symbol.
+
+use crate::{bigint::BigInt, ratio::Ratio, value::Value};
+
+/// Parses a numeric literal, or returns `None` if `literal` is not one.
+pub fn parse(literal: &str) -> Option<Value> {
+    let (negative, signed, body) = match literal.as_bytes().first() {
+        Some(b'-') => (true, true, &literal[1..]),
+        Some(b'+') => (false, true, &literal[1..]),
+        _ => (false, false, literal),
+    };
+
+    match body {
+        "inf.0" if signed => return Some(Value::Float(if negative { f64::NEG_INFINITY } else { f64::INFINITY })),
+        "nan.0" if signed => return Some(Value::Float(f64::NAN)),
+        _ => {},
+    }
+
+    let radix = [("0x", 16), ("0X", 16), ("0o", 8), ("0O", 8), ("0b", 2), ("0B", 2)]
+        .into_iter()
+        .find_map(|(prefix, radix)| body.strip_prefix(prefix).map(|digits| (digits, radix)));
+    if let Some((digits, radix)) = radix {
+        let v = BigInt::parse_radix(&strip_separators(digits, radix)?, radix)?;
+        return Some(Value::bigint(if negative { -&v } else { v }));
+    }
+
+    if let Some((numer, denom)) = body.split_once('/') {
+        let numer = BigInt::parse(&strip_separators(numer, 10)?)?;
+        let denom = BigInt::parse(&strip_separators(denom, 10)?)?;
+        let ratio = Ratio::new(if negative { -&numer } else { numer }, denom)?;
+        return Some(Value::ratio(ratio));
+    }
+
+    let sign = if negative { "-" } else { "" };
+
+    let (mantissa, exponent) = match body.find(['e', 'E']) {
+        Some(i) => (&body[..i], Some(&body[i + 1..])),
+        None => (body, None),
+    };
+    let (int_part, frac_part) = match mantissa.split_once('.') {
+        Some((int_part, frac_part)) => (int_part, Some(frac_part)),
+        None => (mantissa, None),
+    };
+
+    if frac_part.is_none() && exponent.is_none() {
+        let digits = format!("{sign}{}", strip_separators(int_part, 10)?);
+        return match digits.parse::<isize>() {
+            Ok(v) => Some(Value::Integer(v)),
+            Err(_) => BigInt::parse(&digits).map(Value::bigint),
+        };
+    }
+
+    let int_part = optional_digits(int_part)?;
+    let frac_part = optional_digits(frac_part.unwrap_or_default())?;
+    if int_part.is_empty() && frac_part.is_empty() {
+        return None;
+    }
+
+    let exponent = match exponent {
+        Some(exponent) => {
+            let (exponent_sign, digits) = match exponent.as_bytes().first() {
+                Some(b'-') => ("-", &exponent[1..]),
+                Some(b'+') => ("", &exponent[1..]),
+                _ => ("", exponent),
+            };
+            format!("{exponent_sign}{}", strip_separators(digits, 10)?)
+        },
+        None => "0".to_string(),
+    };
+
+    format!("{sign}0{int_part}.{frac_part}0e{exponent}").parse().ok().map(Value::Float)
+}
+
+/// Removes `_` separators from a non-empty sequence of digits in `radix`, or returns `None`
+/// if there are other characters or separators that are not between two digits.
+fn strip_separators(digits: &str, radix: u32) -> Option<String> {
+    let mut result = String::with_capacity(digits.len());
+    let mut previous_digit = false;
+
+    for c in digits.chars() {
+        if c == '_' {
+            if !previous_digit {
+                return None;
+            }
+            previous_digit = false;
+        } else if c.is_digit(radix) {
+            result.push(c);
+            previous_digit = true;
+        } else {
+            return None;
+        }
+    }
+
+    previous_digit.then_some(result)
+}
+
+/// Like [`strip_separators`] for decimal digits, but allows an empty sequence.
+fn optional_digits(digits: &str) -> Option<String> {
+    if digits.is_empty() {
+        Some(String::new())
+    } else {
+        strip_separators(digits, 10)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn integers() {
+        assert_eq!(Some(Value::Integer(42)), parse("42"));
+        assert_eq!(Some(Value::Integer(-42)), parse("-42"));
+        assert_eq!(Some(Value::Integer(42)), parse("+42"));
+        assert_eq!(Some(Value::Integer(1_000_000)), parse("1_000_000"));
+        assert_eq!(Some(Value::Integer(255)), parse("0xff"));
+        assert_eq!(Some(Value::Integer(-255)), parse("-0xFF"));
+        assert_eq!(Some(Value::Integer(0xdead_beef)), parse("0xdead_beef"));
+        assert_eq!(Some(Value::Integer(8)), parse("0o10"));
+        assert_eq!(Some(Value::Integer(5)), parse("0b101"));
+        assert_eq!(Some(Value::Integer(isize::MIN)), parse("-0x8000000000000000"));
+        assert_eq!(Some("18446744073709551616".to_string()), parse("0x1_0000_0000_0000_0000").map(|v| v.to_string()));
+    }
+
+    #[test]
+    fn floats() {
+        assert_eq!(Some(Value::Float(1.5)), parse("1.5"));
+        assert_eq!(Some(Value::Float(-0.5)), parse("-.5"));
+        assert_eq!(Some(Value::Float(5.0)), parse("5."));
+        assert_eq!(Some(Value::Float(1e10)), parse("1e10"));
+        assert_eq!(Some(Value::Float(2.5e-3)), parse("2.5E-3"));
+        assert_eq!(Some(Value::Float(1e3)), parse("1e+3"));
+        assert_eq!(Some(Value::Float(1000.25)), parse("1_000.25"));
+        assert_eq!(Some(Value::Float(f64::INFINITY)), parse("+inf.0"));
+        assert_eq!(Some(Value::Float(f64::NEG_INFINITY)), parse("-inf.0"));
+        assert!(matches!(parse("+nan.0"), Some(Value::Float(v)) if v.is_nan()));
+    }
+
+    #[test]
+    fn ratios() {
+        assert_eq!(Some("1/3".to_string()), parse("2/6").map(|v| v.to_string()));
+        assert_eq!(Some("-1/3".to_string()), parse("-1/3").map(|v| v.to_string()));
+        assert_eq!(Some(Value::Integer(2)), parse("4/2"));
+        assert_eq!(None, parse("1/0"));
+        assert_eq!(None, parse("1/-3"));
+    }
+
+    #[test]
+    fn not_numbers() {
+        for s in ["inf", "nan", "infinity", "NaN", "inf.0", "+inf", "-", "+", ".", "e", "e10", "1e", "1.2.3",
+                  "_1", "1_", "1__0", "0x", "0x_1", "0b102", "1.5e1.5", "--1", "1-2", "/", "1/", "abc"] {
+            assert_eq!(None, parse(s), "{s}");
+        }
+    }
+}
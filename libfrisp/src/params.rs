@@ -0,0 +1,193 @@
+use crate::{ast::AstNode, env::{Env, Environment}, pattern::Pattern, value::{ConstVal, Value}, Arity, Error};
+
+/// Parameter list of a lambda or macro:
+/// `(required... &optional optional... &rest rest &key key...)`.
+///
+/// Required parameters can be [`Pattern`]s that destructure their argument.
+/// Optional and key parameters are either a symbol or a `(symbol default)` list. If their
+/// argument is missing, they are bound to the value of `default`, or to `()` if there is none.
+/// The rest parameter is bound to a list of all arguments after the optional ones.
+/// Key parameters are passed as `:name value` pairs after the optional arguments;
+/// optional parameters stop taking arguments at the first keyword if there are key parameters.
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct Params {
+    required: Vec<Pattern>,
+    optional: Vec<(String, Option<AstNode>)>,
+    rest: Option<String>,
+    key: Vec<(String, Option<AstNode>)>,
+}
+
+enum Section {
+    Required,
+    Optional,
+    Rest,
+    AfterRest,
+    Key,
+}
+
+impl Params {
+
+    pub fn parse(params: &AstNode) -> Result<Params, Error> {
+        let list = params.to_owned().try_to_list().map_err(|n| Error::EvalError(format!("not a list: {n:?}")))?;
+
+        let mut result = Params::default();
+        let mut section = Section::Required;
+
+        for param in list {
+            match (&section, param) {
+                (Section::Required, AstNode::Symbol(s)) if s == "&optional" => section = Section::Optional,
+                (Section::Required | Section::Optional, AstNode::Symbol(s)) if s == "&rest" => section = Section::Rest,
+                (Section::Required | Section::Optional | Section::AfterRest, AstNode::Symbol(s)) if s == "&key" => section = Section::Key,
+                (Section::Required, param) => result.required.push(Pattern::parse(&param)?),
+                (Section::Optional, param) => result.optional.push(Self::parse_with_default(param)?),
+                (Section::Rest, AstNode::Symbol(s)) if !s.starts_with('&') => {
+                    result.rest = Some(s);
+                    section = Section::AfterRest;
+                },
+                (Section::Key, param) => result.key.push(Self::parse_with_default(param)?),
+                (_, n) => return Err(Error::EvalError(format!("invalid parameter: {n:?}"))),
+            }
+        }
+
+        if let Section::Rest = section {
+            return Err(Error::EvalError("missing name after &rest".to_string()));
+        }
+
+        Ok(result)
+    }
+
+    fn parse_with_default(param: AstNode) -> Result<(String, Option<AstNode>), Error> {
+        match param {
+            AstNode::Symbol(s) if !s.starts_with('&') => Ok((s, None)),
+            AstNode::List(l) => match &l[..] {
+                [AstNode::Symbol(s), default] => Ok((s.to_owned(), Some(default.to_owned()))),
+                _ => Err(Error::EvalError(format!("invalid parameter: {l:?}"))),
+            },
+            n => Err(Error::EvalError(format!("invalid parameter: {n:?}"))),
+        }
+    }
+
+    pub fn arity(&self) -> Arity {
+        let min = self.required.len();
+        if self.rest.is_some() || !self.key.is_empty() {
+            Arity::AtLeast(min)
+        } else if self.optional.is_empty() {
+            Arity::Exactly(min)
+        } else {
+            Arity::Between(min, min + self.optional.len())
+        }
+    }
+
+    /// Binds `args` to the parameters in `env`.
+    ///
+    /// Returns the optional and key parameters without an argument whose default still has to be
+    /// evaluated and bound, in order. Their defaults can refer to all parameters before them.
+    pub fn bind(&self, env: &mut Environment, args: Vec<Value>) -> Result<Vec<(String, AstNode)>, Error> {
+        let arity = self.arity();
+        if !arity.accepts(args.len()) {
+            return Err(Error::VarEvalArgNumError { expected: arity, actual: args.len() });
+        }
+
+        let mut args = args.into_iter().peekable();
+
+        for pattern in &self.required {
+            #[cfg(feature = "log")]
+            println!("local env setting {pattern}");
+            pattern.bind(env, args.next().unwrap_or_default())?;
+        }
+
+        let mut defaults = Vec::new();
+
+        for (name, default) in &self.optional {
+            let stop_at_keyword = !self.key.is_empty() && matches!(args.peek(), Some(Value::Keyword(_)));
+            match (args.next_if(|_| !stop_at_keyword), default) {
+                (Some(value), _) => env.insert_var(name, ConstVal::from(value)),
+                (None, Some(default)) => defaults.push((name.to_owned(), default.to_owned())),
+                (None, None) => env.insert_var(name, ConstVal::from(Value::Unit)),
+            }
+        }
+
+        let remaining: Vec<Value> = args.collect();
+
+        if !self.key.is_empty() {
+            let mut keyword_args = KeywordArgs::from_pairs(remaining.clone())?;
+
+            for (name, default) in &self.key {
+                match (keyword_args.take(name), default) {
+                    (Some(value), _) => env.insert_var(name, ConstVal::from(value)),
+                    (None, Some(default)) => defaults.push((name.to_owned(), default.to_owned())),
+                    (None, None) => env.insert_var(name, ConstVal::from(Value::Unit)),
+                }
+            }
+
+            if self.rest.is_none() {
+                keyword_args.finish()?;
+            }
+        }
+
+        if let Some(name) = &self.rest {
+            env.insert_var(name, ConstVal::from(Value::List(remaining.into())));
+        }
+
+        Ok(defaults)
+    }
+
+}
+
+/// Keyword arguments of a call, given as `:name value` pairs.
+///
+/// Native [`Variable`](crate::value::Variable)s can use [`KeywordArgs::split`] to separate them from
+/// the positional arguments, [`KeywordArgs::take`] the ones they know and reject the rest with
+/// [`KeywordArgs::finish`].
+#[derive(Debug, Default)]
+pub struct KeywordArgs(Vec<(String, Value)>);
+
+impl KeywordArgs {
+
+    /// Reads keyword arguments from a sequence of `:name value` pairs.
+    pub fn from_pairs(args: impl IntoIterator<Item = Value>) -> Result<KeywordArgs, Error> {
+        let mut args = args.into_iter();
+        let mut pairs = Vec::new();
+
+        while let Some(keyword) = args.next() {
+            let Value::Keyword(name) = keyword else {
+                return Err(Error::VarEvalError(format!("expected a keyword but got {keyword:?}")));
+            };
+            let value = args.next().ok_or_else(|| Error::VarEvalError(format!("missing value for keyword argument :{name}")))?;
+            pairs.push((name, value));
+        }
+
+        Ok(KeywordArgs(pairs))
+    }
+
+    /// Splits `args` at the first keyword into the positional arguments before it and the keyword arguments from there on.
+    pub fn split(mut args: Vec<Value>) -> Result<(Vec<Value>, KeywordArgs), Error> {
+        let first_keyword = args.iter().position(|v| matches!(v, Value::Keyword(_))).unwrap_or(args.len());
+        let keyword_args = args.split_off(first_keyword);
+        Ok((args, KeywordArgs::from_pairs(keyword_args)?))
+    }
+
+    /// Removes the keyword argument `name`. If it was given more than once, the first value is returned.
+    pub fn take(&mut self, name: &str) -> Option<Value> {
+        let mut value = None;
+        self.0.retain_mut(|(n, v)| {
+            if n != name {
+                return true;
+            }
+            if value.is_none() {
+                value = Some(std::mem::take(v));
+            }
+            false
+        });
+        value
+    }
+
+    /// Fails if any keyword arguments were not taken.
+    pub fn finish(self) -> Result<(), Error> {
+        match self.0.first() {
+            Some((name, _)) => Err(Error::VarEvalError(format!("unknown keyword argument :{name}"))),
+            None => Ok(()),
+        }
+    }
+
+}
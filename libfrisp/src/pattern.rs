@@ -0,0 +1,148 @@
+use std::fmt::Display;
+
+use crate::{ast::AstNode, env::{misc::TypeOf, Environment}, list::List, value::Value, Error};
+
+/// Pattern describing the shape of a value, used to destructure values in bindings and `match`.
+///
+/// - `_` matches anything without binding it.
+/// - A symbol matches anything and binds it to the symbol.
+/// - A literal like `1`, `"a"`, `#t` or `:key`, or a quoted datum like `'sym`, matches an equal value.
+/// - `(? type pattern)` matches a value whose `type-of` is `type` and that matches `pattern`.
+///   The pattern can be omitted.
+/// - `(a (b c) &rest more)` matches a list with an element for each pattern, and any number of
+///   further elements that are matched as a list by the pattern after `&rest`.
+#[derive(Debug, PartialEq, Clone)]
+pub enum Pattern {
+    Wildcard,
+    Symbol(String),
+    Literal(Value),
+    Type {
+        type_name: String,
+        pattern: Box<Pattern>,
+    },
+    List {
+        items: Vec<Pattern>,
+        rest: Option<Box<Pattern>>,
+    },
+}
+
+impl Pattern {
+
+    pub fn parse(node: &AstNode) -> Result<Pattern, Error> {
+        match node {
+            AstNode::Symbol(s) if s == "_" => Ok(Pattern::Wildcard),
+            AstNode::Symbol(s) if !s.starts_with('&') => Ok(Pattern::Symbol(s.to_owned())),
+            AstNode::Value(value) => Ok(Pattern::Literal(value.clone())),
+            AstNode::List(list) => match &list[..] {
+                [AstNode::Symbol(s), datum] if s == "quote" => Ok(Pattern::Literal(datum.quote())),
+                [AstNode::Symbol(s), AstNode::Symbol(type_name), pattern @ ..] if s == "?" => {
+                    let pattern = match pattern {
+                        [] => Pattern::Wildcard,
+                        [pattern] => Pattern::parse(pattern)?,
+                        _ => return Err(Error::EvalError(format!("invalid type pattern: {list:?}"))),
+                    };
+                    Ok(Pattern::Type { type_name: type_name.to_owned(), pattern: Box::new(pattern) })
+                },
+                list => Self::parse_list(list),
+            },
+            n => Err(Error::EvalError(format!("invalid pattern: {n:?}"))),
+        }
+    }
+
+    fn parse_list(list: &[AstNode]) -> Result<Pattern, Error> {
+        let mut items = Vec::new();
+        let mut rest = None;
+        let mut nodes = list.iter();
+
+        while let Some(node) = nodes.next() {
+            match node {
+                AstNode::Symbol(s) if s == "&rest" => {
+                    let pattern = nodes.next().ok_or(Error::EvalError("missing pattern after &rest".to_string()))?;
+                    if let Some(n) = nodes.next() {
+                        return Err(Error::EvalError(format!("invalid pattern after &rest: {n:?}")));
+                    }
+                    rest = Some(Box::new(Pattern::parse(pattern)?));
+                },
+                node => items.push(Pattern::parse(node)?),
+            }
+        }
+
+        Ok(Pattern::List { items, rest })
+    }
+
+    /// Matches `value` against the pattern, collecting the values of its symbols in `bindings`.
+    /// Returns `false` if `value` does not have the shape of the pattern.
+    pub fn destructure(&self, value: &Value, bindings: &mut Vec<(String, Value)>) -> bool {
+        match self {
+            Pattern::Wildcard => true,
+            Pattern::Symbol(name) => {
+                bindings.push((name.to_owned(), value.clone()));
+                true
+            },
+            Pattern::Literal(literal) => literal == value,
+            Pattern::Type { type_name, pattern } => {
+                TypeOf::type_str(value) == type_name && pattern.destructure(value, bindings)
+            },
+            Pattern::List { items, rest } => {
+                let mut list = match value {
+                    Value::List(list) => list.clone(),
+                    Value::Unit => List::new(),
+                    _ => return false,
+                };
+
+                for item in items {
+                    let Some((value, tail)) = list.split_first() else {
+                        return false;
+                    };
+                    if !item.destructure(value, bindings) {
+                        return false;
+                    }
+                    list = tail;
+                }
+
+                match rest {
+                    Some(rest) => rest.destructure(&Value::List(list), bindings),
+                    None => list.is_empty(),
+                }
+            },
+        }
+    }
+
+    /// Binds the parts of `value` to the symbols of the pattern in `env`,
+    /// or fails if `value` does not have the shape of the pattern.
+    pub fn bind(&self, env: &mut Environment, value: Value) -> Result<(), Error> {
+        if let Pattern::Symbol(name) = self {
+            env.insert_value(name, value);
+            return Ok(());
+        }
+
+        let mut bindings = Vec::new();
+        if !self.destructure(&value, &mut bindings) {
+            return Err(Error::EvalError(format!("cannot destructure {value} with pattern {self}")));
+        }
+
+        for (name, value) in bindings {
+            env.insert_value(name, value);
+        }
+        Ok(())
+    }
+
+}
+
+impl Display for Pattern {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Pattern::Wildcard => write!(f, "_"),
+            Pattern::Symbol(name) => write!(f, "{name}"),
+            Pattern::Literal(value) => write!(f, "{value}"),
+            Pattern::Type { type_name, pattern } => write!(f, "(? {type_name} {pattern})"),
+            Pattern::List { items, rest } => {
+                let mut parts: Vec<_> = items.iter().map(|p| p.to_string()).collect();
+                if let Some(rest) = rest {
+                    parts.push(format!("&rest {rest}"));
+                }
+                write!(f, "({})", parts.join(" "))
+            },
+        }
+    }
+}
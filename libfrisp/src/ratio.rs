@@ -0,0 +1,274 @@
+use std::{cmp::Ordering, fmt::Display, ops::{Add, Mul, Neg, Sub}};
+
+use crate::bigint::BigInt;
+
+/// Exact fraction of two integers.
+///
+/// Always in lowest terms with a positive denominator, so equal fractions are equal structurally.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Ratio {
+    numer: BigInt,
+    denom: BigInt,
+}
+
+impl Ratio {
+
+    /// Creates the fraction `numer/denom`, or `None` if `denom` is zero.
+    pub fn new(numer: BigInt, denom: BigInt) -> Option<Ratio> {
+        if denom.is_zero() {
+            return None;
+        }
+        let gcd = numer.gcd(&denom);
+        let (numer, _) = numer.div_rem(&gcd)?;
+        let (denom, _) = denom.div_rem(&gcd)?;
+        if denom.is_negative() {
+            Some(Ratio { numer: -&numer, denom: -&denom })
+        } else {
+            Some(Ratio { numer, denom })
+        }
+    }
+
+    /// Converts a float exactly, or returns `None` if it is infinite or NaN.
+    pub fn from_f64(value: f64) -> Option<Ratio> {
+        if !value.is_finite() {
+            return None;
+        }
+
+        let bits = value.to_bits();
+        let biased_exponent = ((bits >> 52) & 0x7ff) as usize;
+        let fraction = (bits & ((1 << 52) - 1)) as isize;
+        // Subnormal numbers have no implicit leading one and the exponent of the smallest normal number.
+        let (mantissa, exponent) = match biased_exponent {
+            0 => (fraction, 1),
+            e => (fraction | 1 << 52, e),
+        };
+
+        let numer = BigInt::from(if value.is_sign_negative() { -mantissa } else { mantissa });
+        // The mantissa is an integer, so the value is scaled by 2 to the power of the exponent minus the fraction bits.
+        match exponent.checked_sub(1075) {
+            Some(shift) => Some(Ratio::from(numer.shl(shift))),
+            None => Ratio::new(numer, BigInt::from(1).shl(1075 - exponent)),
+        }
+    }
+
+    pub fn numer(&self) -> &BigInt {
+        &self.numer
+    }
+
+    pub fn denom(&self) -> &BigInt {
+        &self.denom
+    }
+
+    pub fn is_integer(&self) -> bool {
+        self.denom == BigInt::from(1)
+    }
+
+    /// Returns the integer part, rounding towards zero.
+    pub fn trunc(&self) -> BigInt {
+        self.div_rem().0
+    }
+
+    pub fn floor(&self) -> BigInt {
+        let (trunc, rem) = self.div_rem();
+        if rem.is_negative() { &trunc - &BigInt::from(1) } else { trunc }
+    }
+
+    pub fn ceil(&self) -> BigInt {
+        let (trunc, rem) = self.div_rem();
+        if rem.is_zero() || rem.is_negative() { trunc } else { &trunc + &BigInt::from(1) }
+    }
+
+    /// Divides the numerator by the denominator, rounding towards zero.
+    fn div_rem(&self) -> (BigInt, BigInt) {
+        self.numer.div_rem(&self.denom).unwrap_or_else(|| unreachable!("denominators are never zero"))
+    }
+
+    /// Rounds to the nearest integer, and halfway cases away from zero like [`f64::round`].
+    pub fn round(&self) -> BigInt {
+        let two = BigInt::from(2);
+        let doubled = &(&self.numer.abs() * &two) + &self.denom;
+        let rounded = doubled.div_rem(&(&self.denom * &two)).map(|(quotient, _)| quotient).unwrap_or_default();
+        if self.numer.is_negative() { -&rounded } else { rounded }
+    }
+
+    pub fn abs(&self) -> Ratio {
+        Ratio { numer: self.numer.abs(), denom: self.denom.clone() }
+    }
+
+    /// Returns the nearest `f64`, which is infinite or zero if the value is out of its range.
+    pub fn to_f64(&self) -> f64 {
+        let (numer_bits, denom_bits) = (self.numer.bits() as i64, self.denom.bits() as i64);
+        if numer_bits < 1024 && denom_bits < 1024 {
+            return self.numer.to_f64() / self.denom.to_f64();
+        }
+
+        // Divides with the numerator scaled so that the quotient has 64 bits, which are
+        // converted exactly enough, and scales the quotient back afterwards.
+        let shift = 64 + denom_bits - numer_bits;
+        let numer = self.numer.abs();
+        let quotient = if shift >= 0 {
+            numer.shl(shift as usize).div_rem(&self.denom)
+        } else {
+            numer.div_rem(&self.denom.shl(-shift as usize))
+        };
+        let quotient = quotient.map_or(f64::NAN, |(quotient, _)| quotient.to_f64());
+
+        // Scales in two steps, as a single power of two could be out of range when the result is not.
+        let scale = -shift.clamp(-4096, 4096) as i32;
+        let magnitude = quotient * 2f64.powi(scale / 2) * 2f64.powi(scale - scale / 2);
+        if self.numer.is_negative() { -magnitude } else { magnitude }
+    }
+
+    /// Divides by `rhs`, or returns `None` if `rhs` is zero.
+    pub fn checked_div(&self, rhs: &Ratio) -> Option<Ratio> {
+        Ratio::new(&self.numer * &rhs.denom, &self.denom * &rhs.numer)
+    }
+
+}
+
+impl From<BigInt> for Ratio {
+    fn from(value: BigInt) -> Self {
+        Ratio { numer: value, denom: BigInt::from(1) }
+    }
+}
+
+impl Display for Ratio {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}/{}", self.numer, self.denom)
+    }
+}
+
+impl PartialOrd for Ratio {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Ratio {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (&self.numer * &other.denom).cmp(&(&other.numer * &self.denom))
+    }
+}
+
+impl Neg for &Ratio {
+    type Output = Ratio;
+
+    fn neg(self) -> Self::Output {
+        Ratio { numer: -&self.numer, denom: self.denom.clone() }
+    }
+}
+
+impl Add for &Ratio {
+    type Output = Ratio;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        let numer = &(&self.numer * &rhs.denom) + &(&rhs.numer * &self.denom);
+        Ratio::new(numer, &self.denom * &rhs.denom).unwrap_or_else(|| unreachable!("denominators are never zero"))
+    }
+}
+
+impl Sub for &Ratio {
+    type Output = Ratio;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        self + &-rhs
+    }
+}
+
+impl Mul for &Ratio {
+    type Output = Ratio;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        Ratio::new(&self.numer * &rhs.numer, &self.denom * &rhs.denom).unwrap_or_else(|| unreachable!("denominators are never zero"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ratio(numer: isize, denom: isize) -> Ratio {
+        Ratio::new(BigInt::from(numer), BigInt::from(denom)).unwrap()
+    }
+
+    #[test]
+    fn normalizes() {
+        assert_eq!("1/3", ratio(2, 6).to_string());
+        assert_eq!("-1/3", ratio(2, -6).to_string());
+        assert_eq!("1/3", ratio(-2, -6).to_string());
+        assert_eq!("0/1", ratio(0, -5).to_string());
+        assert!(ratio(4, 2).is_integer());
+        assert_eq!(None, Ratio::new(BigInt::from(1), BigInt::from(0)));
+    }
+
+    #[test]
+    fn arithmetic() {
+        assert_eq!(ratio(5, 6), &ratio(1, 2) + &ratio(1, 3));
+        assert_eq!(ratio(1, 6), &ratio(1, 2) - &ratio(1, 3));
+        assert_eq!(ratio(1, 6), &ratio(1, 2) * &ratio(1, 3));
+        assert_eq!(Some(ratio(3, 2)), ratio(1, 2).checked_div(&ratio(1, 3)));
+        assert_eq!(None, ratio(1, 2).checked_div(&ratio(0, 1)));
+        assert!(ratio(1, 3) < ratio(1, 2));
+        assert!(ratio(-1, 2) < ratio(-1, 3));
+        assert_eq!(BigInt::from(-2), ratio(-7, 3).trunc());
+    }
+
+    #[test]
+    fn to_f64_of_huge_parts() {
+        let ten = BigInt::from(10);
+        let pow = |base: &BigInt, exp: usize| (0..exp).fold(BigInt::from(1), |acc, _| &acc * base);
+
+        let huge = Ratio::new(pow(&ten, 400), &pow(&ten, 399) + &BigInt::from(1)).unwrap();
+        assert!((huge.to_f64() - 10.0).abs() < 1e-12);
+        assert!(((-&huge).to_f64() + 10.0).abs() < 1e-12);
+
+        let two = BigInt::from(2);
+        let ratio = Ratio::new(&pow(&two, 1100) + &BigInt::from(1), pow(&two, 1099)).unwrap();
+        assert_eq!(2.0, ratio.to_f64());
+
+        let big = Ratio::new(&pow(&two, 1100) + &BigInt::from(1), pow(&two, 100)).unwrap();
+        assert_eq!(2f64.powi(1000), big.to_f64());
+        assert_eq!(f64::INFINITY, Ratio::new(pow(&two, 2000), BigInt::from(3)).unwrap().to_f64());
+
+        let tiny = Ratio::new(BigInt::from(3), pow(&two, 1060)).unwrap();
+        assert_eq!(3.0 * 2f64.powi(-530) * 2f64.powi(-530), tiny.to_f64());
+        assert_eq!(0.0, Ratio::new(BigInt::from(1), pow(&two, 1100)).unwrap().to_f64());
+    }
+
+    #[test]
+    fn rounding() {
+        assert_eq!(BigInt::from(-3), ratio(-7, 3).floor());
+        assert_eq!(BigInt::from(2), ratio(7, 3).floor());
+        assert_eq!(BigInt::from(-2), ratio(-7, 3).ceil());
+        assert_eq!(BigInt::from(3), ratio(7, 3).ceil());
+        assert_eq!(BigInt::from(2), ratio(7, 3).round());
+        assert_eq!(BigInt::from(3), ratio(5, 2).round());
+        assert_eq!(BigInt::from(-3), ratio(-5, 2).round());
+        assert_eq!(BigInt::from(-2), ratio(-5, 3).round());
+    }
+
+    #[test]
+    fn from_f64() {
+        assert_eq!(Some(ratio(3, 2)), Ratio::from_f64(1.5));
+        assert_eq!(Some(ratio(-1, 1024)), Ratio::from_f64(-0.0009765625));
+        assert_eq!(Some(ratio(0, 1)), Ratio::from_f64(-0.0));
+        assert_eq!(Some(Ratio::from(BigInt::from(1).shl(64))), Ratio::from_f64(18446744073709551616.0));
+        assert_eq!(Some(Ratio::new(BigInt::from(1), BigInt::from(1).shl(1074)).unwrap()), Ratio::from_f64(f64::from_bits(1)));
+        assert_eq!(Some(1e300), Ratio::from_f64(1e300).map(|v| v.to_f64()));
+        assert_eq!(None, Ratio::from_f64(f64::INFINITY));
+        assert_eq!(None, Ratio::from_f64(f64::NAN));
+    }
+
+    #[test]
+    fn rounding_integers() {
+        for v in [-4, 0, 4] {
+            let whole = ratio(v, 1);
+            assert_eq!(BigInt::from(v), whole.floor());
+            assert_eq!(BigInt::from(v), whole.ceil());
+            assert_eq!(BigInt::from(v), whole.round());
+            assert_eq!(BigInt::from(v), whole.trunc());
+        }
+        assert_eq!(BigInt::from(-3), ratio(-6, 2).floor());
+        assert_eq!(BigInt::from(3), ratio(6, 2).ceil());
+    }
+}
@@ -1,5 +1,5 @@
 
-use std::fmt::Debug;
+use std::{fmt::Debug, iter::Peekable};
 
 use crate::Error;
 
@@ -7,12 +7,30 @@ use crate::Error;
 pub enum Token {
     ListStart,
     ListEnd,
+    /// `{`, start of a map literal
+    MapStart,
+    /// `}`, end of a map literal
+    MapEnd,
+    /// `[`, start of a vector literal
+    VectorStart,
+    /// `]`, end of a vector literal
+    VectorEnd,
+    /// `'`, shorthand for `quote`
+    Quote,
+    /// `` ` ``, shorthand for `quasiquote`
+    Quasiquote,
+    /// `,`, shorthand for `unquote`
+    Unquote,
+    /// `,@`, shorthand for `unquote-splicing`
+    UnquoteSplicing,
     String(String),
+    /// `#\a`, a character literal. Besides single characters, `#\space`, `#\newline` and `#\tab` are supported.
+    Char(char),
     Symbol(String),
 }
 
-pub struct TokenStream<I> {
-    iter: I,
+pub struct TokenStream<I: Iterator> {
+    iter: Peekable<I>,
     next_token: Option<Token>,
 }
 
@@ -23,7 +41,7 @@ pub trait FrispSymbolChar {
 impl FrispSymbolChar for char {
     fn is_frisp_symbol(&self) -> bool {
         match self {
-            '(' | ')' => false,
+            '(' | ')' | '{' | '}' | '[' | ']' => false,
             c if c.is_ascii_alphanumeric() => true,
             c if c.is_ascii_punctuation() => true,
             _ => false,
@@ -31,17 +49,95 @@ impl FrispSymbolChar for char {
     }
 }
 
-impl<I> TokenStream<I> {
+impl Token {
+
+    /// Returns the token for a character that also ends a symbol.
+    fn delimiter(c: char) -> Option<Token> {
+        match c {
+            '(' => Some(Token::ListStart),
+            ')' => Some(Token::ListEnd),
+            '{' => Some(Token::MapStart),
+            '}' => Some(Token::MapEnd),
+            '[' => Some(Token::VectorStart),
+            ']' => Some(Token::VectorEnd),
+            _ => None,
+        }
+    }
+
+}
+
+impl<I: Iterator> TokenStream<I> {
 
     pub fn new(iter: I) -> TokenStream<I> {
         TokenStream {
-            iter,
+            iter: iter.peekable(),
             next_token: None,
         }
     }
 
 }
 
+impl<I> TokenStream<I>
+where I: Iterator<Item = char> {
+
+    /// Reads a `#t`, `#f`, `#true`, `#false` or `#\a` literal after its `#`. Returns `None` if the
+    /// `#` starts a comment instead, which is the case if it is followed by whitespace. A comment
+    /// runs to the end of the line.
+    ///
+    /// A literal has to be followed by whitespace, a delimiter or the end of the input.
+    fn hash_literal(&mut self) -> Option<Result<Token, Error>> {
+        if self.iter.peek().is_none_or(|c| c.is_whitespace()) {
+            return None;
+        }
+
+        if self.iter.next_if_eq(&'\\').is_some() {
+            return Some(self.char_literal());
+        }
+
+        let word = self.literal_rest();
+        match word.as_str() {
+            "t" | "f" | "true" | "false" => Some(Ok(Token::Symbol(format!("#{word}")))),
+            _ => Some(Err(Error::TokenizerError(format!("invalid literal: #{word}")))),
+        }
+    }
+
+    /// Reads a character literal after its `#\`.
+    fn char_literal(&mut self) -> Result<Token, Error> {
+        let c = self.iter.next().ok_or_else(|| Error::TokenizerError("EOF while reading character".to_string()))?;
+
+        let mut name = c.to_string();
+        if c.is_alphanumeric() {
+            while let Some(c) = self.iter.next_if(|c| c.is_alphanumeric()) {
+                name.push(c);
+            }
+        }
+
+        match name.as_str() {
+            "space" => return Ok(Token::Char(' ')),
+            "newline" => return Ok(Token::Char('\n')),
+            "tab" => return Ok(Token::Char('\t')),
+            _ if name.chars().count() > 1 => return Err(Error::TokenizerError(format!("unknown character name: {name}"))),
+            _ => {},
+        }
+
+        let rest = self.literal_rest();
+        if !rest.is_empty() {
+            return Err(Error::TokenizerError(format!("invalid character literal: #\\{c}{rest}")));
+        }
+        Ok(Token::Char(c))
+    }
+
+    /// Reads the rest of a literal up to whitespace, a delimiter or the end of the input.
+    fn literal_rest(&mut self) -> String {
+        let mut word = String::new();
+        while let Some(c) = self.iter.next_if(|c| !c.is_whitespace() && Token::delimiter(*c).is_none()) {
+            word.push(c);
+        }
+        word
+    }
+
+}
+
 impl<I> Debug for TokenStream<I>
 where I: Debug + Iterator<Item = char> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
@@ -63,7 +159,11 @@ where I: Iterator<Item = char> {
             match c {
                 w if w.is_whitespace() => continue,
                 '#' => {
-                    while let Some(c) = self.iter.next() {
+                    if let Some(token) = self.hash_literal() {
+                        return Some(token);
+                    }
+
+                    for c in self.iter.by_ref() {
                         if c.is_ascii_control() {
                             break;
                         }
@@ -71,6 +171,18 @@ where I: Iterator<Item = char> {
                 }
                 '(' => return Some(Ok(Token::ListStart)),
                 ')' => return Some(Ok(Token::ListEnd)),
+                '{' => return Some(Ok(Token::MapStart)),
+                '}' => return Some(Ok(Token::MapEnd)),
+                '[' => return Some(Ok(Token::VectorStart)),
+                ']' => return Some(Ok(Token::VectorEnd)),
+                '\'' => return Some(Ok(Token::Quote)),
+                '`' => return Some(Ok(Token::Quasiquote)),
+                ',' => {
+                    if self.iter.next_if_eq(&'@').is_some() {
+                        return Some(Ok(Token::UnquoteSplicing));
+                    }
+                    return Some(Ok(Token::Unquote));
+                },
                 '"' => {
                     let mut buf = String::new();
                     while let Some(c) = self.iter.next() {
@@ -94,14 +206,11 @@ where I: Iterator<Item = char> {
                 c if c.is_frisp_symbol() => {
                     let mut buf = String::new();
                     buf.push(c);
-                    while let Some(c) = self.iter.next() {
+                    for c in self.iter.by_ref() {
                         if c.is_whitespace() {
                             break;
-                        } else if c == '(' {
-                            self.next_token.replace(Token::ListStart);
-                            break;
-                        } else if c == ')' {
-                            self.next_token.replace(Token::ListEnd);
+                        } else if let Some(token) = Token::delimiter(c) {
+                            self.next_token.replace(token);
                             break;
                         } else if c.is_frisp_symbol() {
                             buf.push(c)
@@ -115,7 +224,7 @@ where I: Iterator<Item = char> {
                 e => return Some(Err(Error::TokenizerError(format!("invalid token: {e:?}")))),
             }
         }
-        return None;
+        None
     }
 
 }
@@ -133,4 +242,48 @@ mod tests {
         }
 
     }
+
+    fn tokens(code: &str) -> Vec<String> {
+        TokenStream::new(code.chars()).map(|t| format!("{:?}", t.unwrap())).collect()
+    }
+
+    #[test]
+    fn booleans() {
+        assert_eq!(vec!["ListStart", "Symbol(\"if\")", "Symbol(\"#t\")", "Symbol(\"1\")", "Symbol(\"#false\")", "ListEnd"], tokens("(if #t 1 #false)"));
+        assert_eq!(vec!["Symbol(\"#true\")", "Symbol(\"#f\")"], tokens("#true\n  #f"));
+        assert_eq!(vec!["Symbol(\"#true\")", "Symbol(\"story\")"], tokens("#true story"));
+        assert!(TokenStream::new("#tea".chars()).any(|t| t.is_err()));
+    }
+
+    #[test]
+    fn booleans_at_line_start() {
+        let expected = vec!["ListStart", "Symbol(\"define\")", "Symbol(\"xs\")", "ListStart", "Symbol(\"list\")", "Symbol(\"#t\")", "Symbol(\"#f\")", "ListEnd", "ListEnd"];
+        assert_eq!(expected, tokens("(define xs (list\n  #t #f))"));
+    }
+
+    #[test]
+    fn hash_comments() {
+        assert_eq!(vec!["Symbol(\"1\")"], tokens("# note\n1"));
+        assert_eq!(vec!["Symbol(\"1\")"], tokens("  # #f is false\n1"));
+        assert_eq!(vec!["ListStart", "Symbol(\"a\")", "ListEnd"], tokens("(a) # note"));
+        assert_eq!(vec!["Symbol(\"a\")"], tokens("a #"));
+    }
+
+    #[test]
+    fn chars() {
+        assert_eq!(vec!["ListStart", "Char('a')", "Char(' ')", "Char('(')", "ListEnd"], tokens("(#\\a #\\space #\\()"));
+        assert_eq!(vec!["Char('\\n')"], tokens("#\\newline"));
+        assert!(TokenStream::new("(list #\\foo)".chars()).any(|t| t.is_err()));
+    }
+
+    #[test]
+    fn chars_at_line_start() {
+        let expected = vec!["ListStart", "Symbol(\"list\")", "Char('a')", "Char('b')", "ListEnd"];
+        assert_eq!(expected, tokens("(list\n  #\\a #\\b)"));
+        assert_eq!(vec!["Char('a')", "Symbol(\"is\")"], tokens("#\\a is"));
+        assert_eq!(vec!["Char(' ')", "Symbol(\"1\")"], tokens("#\\  1"));
+        assert!(TokenStream::new("#\\foo".chars()).any(|t| t.is_err()));
+        assert!(TokenStream::new("#\\ab".chars()).any(|t| t.is_err()));
+        assert!(TokenStream::new("#\\".chars()).any(|t| t.is_err()));
+    }
 }
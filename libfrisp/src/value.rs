@@ -1,51 +1,216 @@
 
-use std::{fmt::Display, rc::Rc};
+use std::{cmp::Ordering, collections::BTreeMap, fmt::{Debug, Display}, rc::Rc};
 
-use crate::{ast::AstNode, env::{Env, Environment}, Error};
+use crate::{ast::AstNode, bigint::BigInt, env::{Environment, WeakEnvironment}, eval::Evaluator, list::List, params::Params, ratio::Ratio, Arity, Error};
 
 
+/// A lambda value, closing over the environment it was created in.
 #[derive(Debug, PartialEq, Clone)]
 pub struct Lambda {
-    vars: Vec<String>,
-    body: Vec<AstNode>,
+    params: Rc<Params>,
+    body: Rc<[AstNode]>,
+    env: Environment,
 }
 
 impl Lambda {
 
-    pub fn new(args: Vec<String>, body: Vec<AstNode>) -> Lambda {
-        Lambda { vars: args, body }
+    pub fn new(params: Params, body: Vec<AstNode>, env: Environment) -> Lambda {
+        Lambda { params: Rc::new(params), body: body.into(), env }
+    }
+
+    pub fn params(&self) -> &Params {
+        &self.params
+    }
+
+    pub fn body(&self) -> Rc<[AstNode]> {
+        self.body.clone()
+    }
+
+    /// The environment the lambda was created in.
+    pub fn env(&self) -> &Environment {
+        &self.env
+    }
+
+    /// Returns the lambda without keeping its environment alive, see [`WeakLambda`].
+    pub(crate) fn downgrade(&self) -> WeakLambda {
+        WeakLambda { params: self.params.clone(), body: self.body.clone(), env: self.env.downgrade() }
+    }
+
+    /// Creates the local environment for a call, binding `args` to the lambda's parameters.
+    /// Also returns the optional parameters whose default values still have to be evaluated
+    /// in that environment, see [`Params::bind`].
+    pub fn bind_args(&self, args: Vec<Value>) -> Result<(Environment, Vec<(String, AstNode)>), Error> {
+        let mut local_env = self.env.sub_env();
+
+        #[cfg(feature = "log")]
+        println!("created local_env {local_env:?} from closure env {:?}", self.env);
+
+        let defaults = self.params.bind(&mut local_env, args)?;
+
+        Ok((local_env, defaults))
     }
     
 }
 
+/// A lambda that does not keep its environment alive.
+///
+/// Used to bind a lambda in the scope it closes over, which would otherwise be a reference cycle.
+pub(crate) struct WeakLambda {
+    params: Rc<Params>,
+    body: Rc<[AstNode]>,
+    env: WeakEnvironment,
+}
+
+impl WeakLambda {
+
+    /// Returns the lambda, or `None` if its environment is gone.
+    pub(crate) fn upgrade(&self) -> Option<Lambda> {
+        let env = self.env.upgrade()?;
+        Some(Lambda { params: self.params.clone(), body: self.body.clone(), env })
+    }
+
+}
+
+/// A builtin procedure as a value.
+///
+/// Holds on to the procedure itself rather than its name, so that it can be passed around and
+/// called wherever the name is bound to something else.
+#[derive(Clone)]
+pub struct Native {
+    name: String,
+    var: Rc<dyn Variable>,
+}
+
+impl Native {
+
+    pub fn new(name: impl ToString, var: Rc<dyn Variable>) -> Native {
+        Native { name: name.to_string(), var }
+    }
+
+    /// The name the procedure was bound to when it was turned into a value.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn var(&self) -> Rc<dyn Variable> {
+        self.var.clone()
+    }
+
+}
+
+impl Debug for Native {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("Native").field(&self.name).finish()
+    }
+}
+
+/// Natives are equal if they are the same procedure, regardless of their names.
+impl PartialEq for Native {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.var, &other.var)
+    }
+}
+
+/// A pair whose rest is not a list, see [`Value::Pair`].
 #[derive(Debug, PartialEq, Clone)]
+pub struct Pair(Rc<(Value, Value)>);
+
+impl Pair {
+
+    pub fn new(first: Value, rest: Value) -> Pair {
+        Pair(Rc::new((first, rest)))
+    }
+
+    pub fn first(&self) -> &Value {
+        &self.0.0
+    }
+
+    pub fn rest(&self) -> &Value {
+        &self.0.1
+    }
+
+}
+
+/// Drops a chain of pairs iteratively, like [`List`], so that a long chain does not overflow the stack.
+impl Drop for Pair {
+    fn drop(&mut self) {
+        let Some(pair) = Rc::get_mut(&mut self.0) else {
+            return;
+        };
+        let mut next = std::mem::take(&mut pair.1);
+        while let Value::Pair(mut pair) = next {
+            next = match Rc::get_mut(&mut pair.0) {
+                Some(pair) => std::mem::take(&mut pair.1),
+                None => Value::Unit,
+            };
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Default)]
 pub enum Value {
+    #[default]
     Unit,
+    Bool(bool),
+    /// A Unicode scalar value, written `#\a`.
+    Char(char),
     String(String),
     Integer(isize),
+    /// An integer outside of the range of [`Value::Integer`]. Integers are promoted to big integers
+    /// when they overflow and demoted again when they fit, see [`Value::bigint`].
+    BigInt(Rc<BigInt>),
+    /// A fraction that is not an integer, like `1/3`, see [`Value::ratio`].
+    Ratio(Rc<Ratio>),
     Float(f64),
-    List(Vec<Value>),
+    List(List),
+    /// A pair whose rest is not a list, like `(1 . 2)`.
+    /// Pairs whose rest is a list are always represented as [`Value::List`].
+    Pair(Pair),
+    /// Vector with constant time indexing. `vector-set` and `vector-push` return a changed copy,
+    /// which copies all elements if the vector is still bound elsewhere, like to the variable it
+    /// was read from. `vector-set!` and `vector-push!` change the vector of a variable in place.
+    Vector(Rc<Vec<Value>>),
+    /// Map ordered by its keys, see [`MapKey`]. `hash-set` and `hash-remove` return a changed
+    /// copy, which copies all entries if the map is still bound elsewhere, like to the variable it
+    /// was read from. `hash-set!` and `hash-remove!` change the map of a variable in place.
+    Map(Rc<BTreeMap<MapKey, Value>>),
     Lambda(Rc<Lambda>),
+    Macro(Rc<Lambda>),
+    /// A builtin procedure, which is what the symbol of a builtin like `car` evaluates to.
+    Native(Native),
     SymbolRef(String),
+    /// A `:name` literal, stored without the colon. Evaluates to itself.
+    Keyword(String),
     Error(String),
 }
 
-impl Default for Value {
-    fn default() -> Self {
-        Value::Unit
-    }
-}
-
 impl Value {
 
     pub fn bool(v: bool) -> Value {
-        Value::int(v)
+        Value::Bool(v)
     }
 
     pub fn int<T: Into<isize>>(v: T) -> Value {
         Value::Integer(v.into())
     }
 
+    /// Creates an integer, which is only a [`Value::BigInt`] if it does not fit into a [`Value::Integer`].
+    pub fn bigint(v: BigInt) -> Value {
+        match v.to_isize() {
+            Some(v) => Value::Integer(v),
+            None => Value::BigInt(Rc::new(v)),
+        }
+    }
+
+    /// Creates an exact number, which is only a [`Value::Ratio`] if it is not an integer.
+    pub fn ratio(v: Ratio) -> Value {
+        if v.is_integer() {
+            Value::bigint(v.numer().clone())
+        } else {
+            Value::Ratio(Rc::new(v))
+        }
+    }
+
     pub fn float<T: Into<f64>>(v: T) -> Value {
         Value::Float(v.into())
     }
@@ -54,6 +219,77 @@ impl Value {
         Value::String(v.to_string())
     }
 
+    /// Creates a map from key value pairs. Later pairs replace earlier ones with an equal key.
+    pub fn map(pairs: impl IntoIterator<Item = (Value, Value)>) -> Result<Value, Error> {
+        let map: Result<BTreeMap<_, _>, Error> = pairs.into_iter()
+            .map(|(k, v)| Ok((MapKey::new(k)?, v)))
+            .collect();
+        Ok(Value::Map(Rc::new(map?)))
+    }
+
+    /// Creates a map from alternating keys and values, like a `{k v ...}` literal.
+    pub fn map_of_entries(entries: Vec<Value>) -> Result<Value, Error> {
+        if !entries.len().is_multiple_of(2) {
+            return Err(Error::VarEvalError(format!("missing value for key {:?}", entries[entries.len() - 1])));
+        }
+
+        let mut entries = entries.into_iter();
+        let mut pairs = Vec::new();
+        while let (Some(k), Some(v)) = (entries.next(), entries.next()) {
+            pairs.push((k, v));
+        }
+
+        Value::map(pairs)
+    }
+
+    /// Creates the pair of `first` and `rest`, which is a list if `rest` is a list.
+    pub fn cons(first: Value, rest: Value) -> Value {
+        match rest {
+            Value::List(list) => Value::List(list.cons(first)),
+            Value::Unit => Value::List(List::new().cons(first)),
+            rest => Value::Pair(Pair::new(first, rest)),
+        }
+    }
+
+    /// Returns the elements of a chain of pairs and the value that ends it.
+    pub fn pair_parts(pair: &Pair) -> (Vec<&Value>, &Value) {
+        let mut items = vec![pair.first()];
+        let mut rest = pair.rest();
+        while let Value::Pair(pair) = rest {
+            items.push(pair.first());
+            rest = pair.rest();
+        }
+        (items, rest)
+    }
+
+    /// Returns an integer as [`BigInt`].
+    pub fn to_bigint(&self) -> Option<BigInt> {
+        match self {
+            Value::Integer(v) => Some(BigInt::from(*v)),
+            Value::BigInt(v) => Some(v.as_ref().clone()),
+            _ => None,
+        }
+    }
+
+    /// Returns an integer or ratio as [`Ratio`].
+    pub fn to_ratio(&self) -> Option<Ratio> {
+        match self {
+            Value::Ratio(v) => Some(v.as_ref().clone()),
+            v => v.to_bigint().map(Ratio::from),
+        }
+    }
+
+    /// Returns a number as `f64`.
+    pub fn to_f64(&self) -> Option<f64> {
+        match self {
+            Value::Integer(v) => Some(*v as f64),
+            Value::BigInt(v) => Some(v.to_f64()),
+            Value::Ratio(v) => Some(v.to_f64()),
+            Value::Float(v) => Some(*v),
+            _ => None,
+        }
+    }
+
     pub fn as_str(&self) -> Option<&str> {
         if let Value::String(s) = self {
             Some(s.as_str())
@@ -63,10 +299,10 @@ impl Value {
     }
 
     pub fn require_str(&self) -> Result<&str, Error> {
-        self.as_str().ok_or(Error::VarEvalError(format!("not a string: {self:?}")))
+        self.as_str().ok_or_else(|| Error::VarEvalError(format!("not a string: {self:?}")))
     }
 
-    pub fn as_list(&self) -> Option<&Vec<Value>> {
+    pub fn as_list(&self) -> Option<&List> {
         if let Value::List(list) = self {
             Some(list)
         } else {
@@ -74,14 +310,38 @@ impl Value {
         }
     }
 
-    pub fn require_list(&self) -> Result<&Vec<Value>, Error> {
-        self.as_list().ok_or(Error::VarEvalError(format!("not a list: {self:?}")))
+    pub fn require_list(&self) -> Result<&List, Error> {
+        self.as_list().ok_or_else(|| Error::VarEvalError(format!("not a list: {self:?}")))
+    }
+
+    pub fn as_vector(&self) -> Option<&Vec<Value>> {
+        if let Value::Vector(vector) = self {
+            Some(vector)
+        } else {
+            None
+        }
+    }
+
+    pub fn require_vector(&self) -> Result<&Vec<Value>, Error> {
+        self.as_vector().ok_or_else(|| Error::VarEvalError(format!("not a vector: {self:?}")))
+    }
+
+    pub fn as_map(&self) -> Option<&BTreeMap<MapKey, Value>> {
+        if let Value::Map(map) = self {
+            Some(map)
+        } else {
+            None
+        }
     }
 
-    pub fn to_list(self) -> Option<Vec<Value>> {
+    pub fn require_map(&self) -> Result<&BTreeMap<MapKey, Value>, Error> {
+        self.as_map().ok_or_else(|| Error::VarEvalError(format!("not a map: {self:?}")))
+    }
+
+    pub fn to_list(self) -> Option<List> {
         match self {
             Value::List(list) => Some(list),
-            Value::Unit => Some(Vec::new()),
+            Value::Unit => Some(List::new()),
             _ => None,
         }
     }
@@ -95,20 +355,26 @@ impl Value {
     }
 
     pub fn unwrap_single_value_list(self) -> Value {
-        if let Value::List(l) = self {
-            if l.len() == 1 {
-                return l.into_iter().next().unwrap();
+        if let Value::List(l) = &self {
+            if let Some((first, rest)) = l.split_first() {
+                if rest.is_empty() {
+                    return first.clone();
+                }
             }
-            return Value::List(l);
         }
 
         self
     }
 
-    pub fn to_bool(self) -> Option<bool> {
+    /// Whether the value counts as true in a condition.
+    ///
+    /// `#f` and the empty list `()` are false, every other value is true, including `0` and `""`.
+    pub fn is_truthy(&self) -> bool {
         match self {
-            Value::Integer(i) => Some(i != 0),
-            _ => None,
+            Value::Bool(b) => *b,
+            Value::Unit => false,
+            Value::List(l) => !l.is_empty(),
+            _ => true,
         }
     }
 
@@ -119,17 +385,39 @@ impl Display for Value {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             Value::Unit => write!(f, ""),
+            Value::Bool(v) => write!(f, "{}", if *v { "#t" } else { "#f" }),
+            Value::Char(v) => write!(f, "{v}"),
             Value::String(v) => write!(f, "{v}"),
             Value::Integer(v) => write!(f, "{v}"),
+            Value::BigInt(v) => write!(f, "{v}"),
+            Value::Ratio(v) => write!(f, "{v}"),
             Value::Float(v) => write!(f, "{v}"),
             Value::List(v) => {
                 let s: Vec<_> = v.iter().map(|vv| vv.to_string()).collect();
                 write!(f, "({})", s.join(","))
             },
+            Value::Pair(p) => {
+                let (items, rest) = Value::pair_parts(p);
+                let s: Vec<_> = items.iter().map(|vv| vv.to_string()).collect();
+                write!(f, "({} . {rest})", s.join(","))
+            },
+            Value::Vector(v) => {
+                let s: Vec<_> = v.iter().map(|vv| vv.to_string()).collect();
+                write!(f, "[{}]", s.join(","))
+            },
+            Value::Map(m) => {
+                let s: Vec<_> = m.iter().map(|(k, v)| format!("{} {v}", k.value())).collect();
+                write!(f, "{{{}}}", s.join(","))
+            },
             Value::Lambda(lambda) => {
-                write!(f, "(lambda {:?} {:?})", &lambda.vars, &lambda.body)
+                write!(f, "(lambda {:?} {:?})", &lambda.params, &lambda.body)
             },
+            Value::Macro(lambda) => {
+                write!(f, "(macro {:?} {:?})", &lambda.params, &lambda.body)
+            },
+            Value::Native(native) => write!(f, "#<native {}>", native.name),
             Value::SymbolRef(v) => write!(f, "@{v}"),
+            Value::Keyword(v) => write!(f, ":{v}"),
             Value::Error(e) => write!(f, "Value Error: {e}"),
         }
     }
@@ -141,38 +429,92 @@ impl FromIterator<Value> for Value {
     }
 }
 
-pub trait Variable {
-    fn eval(&self, env: &Environment, args: Vec<Value>) -> Result<Value, Error>;
+/// Key of a [`Value::Map`].
+///
+/// Only values with a total order can be keys: `()`, booleans, integers, characters, strings, keywords, symbols
+/// and lists of keys. Keys of different types are ordered by type in that order, so that iterating
+/// over a map always yields its entries in the same order.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MapKey(Value);
 
-    fn val(&self) -> Option<Value> {
-        None
+impl MapKey {
+
+    pub fn new(value: Value) -> Result<MapKey, Error> {
+        if Self::is_key(&value) {
+            Ok(MapKey(value))
+        } else {
+            Err(Error::VarEvalError(format!("cannot use {value:?} as a map key")))
+        }
     }
-}
 
-impl Variable for Lambda {
-    fn eval(&self, env: &Environment, args: Vec<Value>) -> Result<Value, Error> {
-        let vars = &self.vars;
-        if vars.len() != args.len() {
-            return Err(Error::VarEvalArgNumError { expected: vars.len(), actual: args.len() });
+    fn is_key(value: &Value) -> bool {
+        match value {
+            Value::Unit | Value::Bool(_) | Value::Integer(_) | Value::BigInt(_) | Value::Ratio(_) | Value::Char(_) | Value::String(_) | Value::Keyword(_) | Value::SymbolRef(_) => true,
+            Value::List(l) => l.iter().all(Self::is_key),
+            _ => false,
         }
-        let mut local_env = env.local_env();
+    }
 
-        #[cfg(feature = "log")]
-        println!("created local_env#{:p} from env#{env:p}", &local_env);
+    pub fn value(&self) -> &Value {
+        &self.0
+    }
 
-        for (name, value) in vars.iter().zip(args.into_iter()) {
-            #[cfg(feature = "log")]
-            println!("local env setting {name} to {value}");
-            local_env.insert_var(name.clone(), ConstVal(value));
+    fn rank(value: &Value) -> u8 {
+        match value {
+            Value::Unit => 0,
+            Value::Bool(_) => 1,
+            Value::Integer(_) | Value::BigInt(_) | Value::Ratio(_) => 2,
+            Value::Char(_) => 3,
+            Value::String(_) => 4,
+            Value::Keyword(_) => 5,
+            Value::SymbolRef(_) => 6,
+            Value::List(_) => 7,
+            _ => u8::MAX,
         }
+    }
 
-        let mut last_value = None;
-
-        for stmt in &self.body {
-            last_value = Some(stmt.eval(&mut local_env)?)
+    fn compare(a: &Value, b: &Value) -> Ordering {
+        match (a, b) {
+            (Value::Bool(a), Value::Bool(b)) => a.cmp(b),
+            (Value::Integer(a), Value::Integer(b)) => a.cmp(b),
+            (Value::Integer(_) | Value::BigInt(_), Value::Integer(_) | Value::BigInt(_)) => a.to_bigint().cmp(&b.to_bigint()),
+            (Value::Integer(_) | Value::BigInt(_) | Value::Ratio(_), Value::Integer(_) | Value::BigInt(_) | Value::Ratio(_)) => a.to_ratio().cmp(&b.to_ratio()),
+            (Value::Char(a), Value::Char(b)) => a.cmp(b),
+            (Value::String(a), Value::String(b)) | (Value::Keyword(a), Value::Keyword(b)) | (Value::SymbolRef(a), Value::SymbolRef(b)) => a.cmp(b),
+            (Value::List(a), Value::List(b)) => {
+                a.iter().zip(b).map(|(a, b)| Self::compare(a, b)).find(|o| o.is_ne()).unwrap_or_else(|| a.len().cmp(&b.len()))
+            },
+            (a, b) => Self::rank(a).cmp(&Self::rank(b)),
         }
-        
-        last_value.ok_or(Error::VarEvalError(format!("no value")))
+    }
+
+}
+
+impl Eq for MapKey {}
+
+impl PartialOrd for MapKey {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for MapKey {
+    fn cmp(&self, other: &Self) -> Ordering {
+        Self::compare(&self.0, &other.0)
+    }
+}
+
+pub trait Variable {
+    fn eval(&self, env: &Environment, args: Vec<Value>) -> Result<Value, Error>;
+
+    fn val(&self) -> Option<Value> {
+        None
+    }
+}
+
+impl Variable for Lambda {
+    fn eval(&self, _env: &Environment, args: Vec<Value>) -> Result<Value, Error> {
+        Evaluator::new(self.env.max_depth()).call(self, args)
     }
 }
 
@@ -200,16 +542,17 @@ impl Variable for ConstVal {
             Value::Lambda(lambda) => {
                 lambda.eval(env, args)
             }
+            Value::Native(native) => {
+                native.var.eval(env, args)
+            }
             Value::SymbolRef(sym) => {
                 #[cfg(feature = "log")]
                 println!("getting symbol ref {sym}");
-                let var = env.get_var(&sym)
-                    .ok_or(Error::VarEvalError(format!("unknown symbol: {sym}")))?;
-                var.eval(env, args)
+                env.resolve_var(sym)?.eval(env, args)
             }
             _ => {
                 if !args.is_empty() {
-                    Err(Error::VarEvalArgNumError { expected: 0, actual: args.len() })
+                    Err(Error::VarEvalArgNumError { expected: Arity::Exactly(0), actual: args.len() })
                 } else {
                     #[cfg(feature = "log")]
                     println!("getting value {v:?}");
@@ -228,69 +571,111 @@ impl Variable for ConstVal {
     }
 }
 
+pub(crate) use arithmetic_impl::Operands;
+
 mod arithmetic_impl {
 
 use std::ops::{Add, Sub, Mul, Div};
 
+use crate::{bigint::BigInt, ratio::Ratio, Error};
+
 use super::Value;
 
-// use proc macro
+/// Operands of a binary arithmetic operation, converted to the representation the operation
+/// is done in: integers if both are integers, big integers if both are integers but not both
+/// fit into [`Value::Integer`], ratios if either is a ratio, and floats if either is a float.
+pub(crate) enum Operands {
+    Int(isize, isize),
+    Big(BigInt, BigInt),
+    Ratio(Ratio, Ratio),
+    Float(f64, f64),
+}
+
+impl Operands {
+
+    pub(crate) fn of(v1: &Value, v2: &Value) -> Option<Operands> {
+        match (v1, v2) {
+            (Value::Integer(a), Value::Integer(b)) => Some(Operands::Int(*a, *b)),
+            (Value::Float(_), _) | (_, Value::Float(_)) => Some(Operands::Float(v1.to_f64()?, v2.to_f64()?)),
+            (Value::Ratio(_), _) | (_, Value::Ratio(_)) => Some(Operands::Ratio(v1.to_ratio()?, v2.to_ratio()?)),
+            _ => Some(Operands::Big(v1.to_bigint()?, v2.to_bigint()?)),
+        }
+    }
+
+}
 
 impl Add for Value {
-    type Output = Value;
+    type Output = Result<Value, Error>;
 
     fn add(self, rhs: Self) -> Self::Output {
-        match (self, rhs) {
-            (Value::Integer(v1), Value::Integer(v2)) => Value::Integer(v1 + v2),
-            (Value::Integer(v1), Value::Float(v2)) => Value::Float(v1 as f64 + v2),
-            (Value::Float(v1), Value::Integer(v2)) => Value::Float(v1 + v2 as f64),
-            (Value::Float(v1), Value::Float(v2)) => Value::Float(v1 + v2),
-            (v1, v2) => Value::Error(format!("Cannot add {v1:?} and {v2:?}")),
+        match Operands::of(&self, &rhs) {
+            Some(Operands::Int(v1, v2)) => match v1.checked_add(v2) {
+                Some(v) => Ok(Value::Integer(v)),
+                None => Ok(Value::bigint(&BigInt::from(v1) + &BigInt::from(v2))),
+            },
+            Some(Operands::Big(v1, v2)) => Ok(Value::bigint(&v1 + &v2)),
+            Some(Operands::Ratio(v1, v2)) => Ok(Value::ratio(&v1 + &v2)),
+            Some(Operands::Float(v1, v2)) => Ok(Value::Float(v1 + v2)),
+            None => Err(Error::VarEvalError(format!("Cannot add {self:?} and {rhs:?}"))),
         }
     }
 
 }
 
 impl Sub for Value {
-    type Output = Value;
+    type Output = Result<Value, Error>;
 
     fn sub(self, rhs: Self) -> Self::Output {
-        match (self, rhs) {
-            (Value::Integer(v1), Value::Integer(v2)) => Value::Integer(v1 - v2),
-            (Value::Integer(v1), Value::Float(v2)) => Value::Float(v1 as f64 - v2),
-            (Value::Float(v1), Value::Integer(v2)) => Value::Float(v1 - v2 as f64),
-            (Value::Float(v1), Value::Float(v2)) => Value::Float(v1 - v2),
-            (v1, v2) => Value::Error(format!("cannot sub {v1:?} and {v2:?}")),
+        match Operands::of(&self, &rhs) {
+            Some(Operands::Int(v1, v2)) => match v1.checked_sub(v2) {
+                Some(v) => Ok(Value::Integer(v)),
+                None => Ok(Value::bigint(&BigInt::from(v1) - &BigInt::from(v2))),
+            },
+            Some(Operands::Big(v1, v2)) => Ok(Value::bigint(&v1 - &v2)),
+            Some(Operands::Ratio(v1, v2)) => Ok(Value::ratio(&v1 - &v2)),
+            Some(Operands::Float(v1, v2)) => Ok(Value::Float(v1 - v2)),
+            None => Err(Error::VarEvalError(format!("cannot sub {self:?} and {rhs:?}"))),
         }
     }
 }
 
 impl Mul for Value {
-    type Output = Value;
+    type Output = Result<Value, Error>;
 
     fn mul(self, rhs: Self) -> Self::Output {
-        match (self, rhs) {
-            (Value::Integer(v1), Value::Integer(v2)) => Value::Integer(v1 * v2),
-            (Value::Integer(v1), Value::Float(v2)) => Value::Float(v1 as f64 * v2),
-            (Value::Float(v1), Value::Integer(v2)) => Value::Float(v1 * v2 as f64),
-            (Value::Float(v1), Value::Float(v2)) => Value::Float(v1 * v2),
-            (v1, v2) => Value::Error(format!("cannot mul {v1:?} and {v2:?}")),
+        match Operands::of(&self, &rhs) {
+            Some(Operands::Int(v1, v2)) => match v1.checked_mul(v2) {
+                Some(v) => Ok(Value::Integer(v)),
+                None => Ok(Value::bigint(&BigInt::from(v1) * &BigInt::from(v2))),
+            },
+            Some(Operands::Big(v1, v2)) => Ok(Value::bigint(&v1 * &v2)),
+            Some(Operands::Ratio(v1, v2)) => Ok(Value::ratio(&v1 * &v2)),
+            Some(Operands::Float(v1, v2)) => Ok(Value::Float(v1 * v2)),
+            None => Err(Error::VarEvalError(format!("cannot mul {self:?} and {rhs:?}"))),
         }
     }
 }
 
 impl Div for Value {
-    type Output = Value;
+    type Output = Result<Value, Error>;
 
     fn div(self, rhs: Self) -> Self::Output {
-        match (self, rhs) {
-            (Value::Integer(v1), Value::Integer(v2)) => Value::Integer(v1 / v2),
-            (Value::Integer(v1), Value::Float(v2)) => Value::Float(v1 as f64 / v2),
-            (Value::Float(v1), Value::Integer(v2)) => Value::Float(v1 / v2 as f64),
-            (Value::Float(v1), Value::Float(v2)) => Value::Float(v1 / v2),
-            (v1, v2) => Value::Error(format!("cannot div {v1:?} and {v2:?}")),
+        match Operands::of(&self, &rhs) {
+            Some(Operands::Int(_, 0)) => Err(Error::DivisionByZero),
+            Some(Operands::Int(v1, v2)) if v1.wrapping_rem(v2) == 0 => match v1.checked_div(v2) {
+                Some(v) => Ok(Value::Integer(v)),
+                None => Ok(Value::bigint(-&BigInt::from(v1))),
+            },
+            Some(Operands::Int(v1, v2)) => Ratio::new(BigInt::from(v1), BigInt::from(v2))
+                .map(Value::ratio).ok_or(Error::DivisionByZero),
+            Some(Operands::Big(v1, v2)) => Ratio::new(v1, v2)
+                .map(Value::ratio).ok_or(Error::DivisionByZero),
+            Some(Operands::Ratio(v1, v2)) => v1.checked_div(&v2)
+                .map(Value::ratio).ok_or(Error::DivisionByZero),
+            Some(Operands::Float(v1, v2)) => Ok(Value::Float(v1 / v2)),
+            None => Err(Error::VarEvalError(format!("cannot div {self:?} and {rhs:?}"))),
         }
     }
 }
 
-}
\ No newline at end of file
+}
@@ -59,23 +59,19 @@
 
 (define index-of (lambda (val l)
   (define index-of-w (lambda (n val l)
-    (if (endp l)
-      ()
-      (if (== val (car l))
-        (n)
-        (index-of-w (+ n 1) val (cdr l))
-      )
+    (cond
+      ((endp l) ())
+      ((== val (car l)) n)
+      (else (index-of-w (+ n 1) val (cdr l)))
     )
   ))
   (index-of-w 0 val l)
 ))
 
 (define find-by-key (lambda (key l)
-  (if (endp l)
-    ()
-    (if (== key (car (car l)))
-      (car l)
-      (find-by-key key (cdr l))
-    )
+  (cond
+    ((endp l) ())
+    ((== key (car (car l))) (car l))
+    (else (find-by-key key (cdr l)))
   )
 ))
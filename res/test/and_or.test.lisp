@@ -0,0 +1,35 @@
+(define test-and-returns-decisive-value (lambda ()
+    (assert-eq "a" (and 1 "a"))
+    (assert-eq #f (and 1 #f 2))
+    (assert-eq () (and 1 () 2))
+))
+
+(define test-or-returns-decisive-value (lambda ()
+    (assert-eq 1 (or #f 1 2))
+    (assert-eq #f (or () #f))
+))
+
+(define test-and-or-without-operands (lambda ()
+    (assert-eq (list #t #f) (list (and) (or)))
+))
+
+(define test-and-short-circuits (lambda ()
+    (define l ())
+    (assert-eq #f (and (not (endp l)) (== (car l) 1)))
+))
+
+(define test-or-short-circuits (lambda ()
+    (define x 0)
+    (or 1 (set! x 1))
+    (assert-eq 0 x)
+))
+
+(define test-and-or-variadic (lambda ()
+    (assert-eq 4 (and 1 2 3 4))
+    (assert-eq 4 (or #f () #f 4))
+))
+
+(define test-and-tail-position (lambda ()
+    (define loop (lambda (n) (or (== n 0) (loop (- n 1)))))
+    (assert-eq #t (loop 10000))
+))
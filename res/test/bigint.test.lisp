@@ -0,0 +1,42 @@
+(define fact (lambda (n)
+    (if (< n 2) 1 (* n (fact (- n 1))))
+))
+
+(define test-overflow-promotes (lambda ()
+    (assert-eq 9223372036854775808 (+ 9223372036854775807 1))
+    (assert-eq -9223372036854775809 (- -9223372036854775808 1))
+    (assert-eq 9223372036854775808 (/ -9223372036854775808 -1))
+    (assert-eq "integer" (type-of (+ 9223372036854775807 1)))
+))
+
+(define test-factorial (lambda ()
+    (assert-eq 265252859812191058636308480000000 (fact 30))
+    (assert-eq "265252859812191058636308480000000" (to-string (fact 30)))
+    (assert-eq 30 (/ (fact 30) (fact 29)))
+))
+
+(define test-demotes (lambda ()
+    (define big (fact 25))
+    (assert-eq 0 (- big big))
+    (assert-eq 25 (/ big (fact 24)))
+    (assert-eq 1 (+ (- big big) 1))
+))
+
+(define test-compare-and-mod (lambda ()
+    (assert (< 1 (fact 25)))
+    (assert (> (fact 26) (fact 25)))
+    (assert (< (- 0 (fact 25)) 0))
+    (assert-eq 0 (mod (fact 25) (fact 20)))
+    (assert-eq 1 (mod (+ (fact 25) 1) 5))
+    (assert-eq "error" (type-of (try (mod (fact 25) 0))))
+    (assert-eq "error" (type-of (try (/ (fact 25) 0))))
+))
+
+(define test-parse-int (lambda ()
+    (assert-eq (fact 30) (parse-int "265252859812191058636308480000000"))
+    (assert-eq 42 (parse-int "42"))
+))
+
+(define test-map-key (lambda ()
+    (assert-eq "big" (hash-get (hash-set {} (fact 25) "big") (fact 25)))
+))
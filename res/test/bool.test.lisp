@@ -0,0 +1,27 @@
+# booleans and truthiness
+
+(define test-bool-literals (lambda ()
+    (assert-eq "bool" (type-of #t))
+    (assert-eq (list #t #f) (list #true #false))
+    (assert-eq #t (not #f))
+))
+
+(define test-predicates-return-bools (lambda ()
+    (assert-eq #t (== 1 1))
+    (assert-eq #f (< 2 1))
+    (assert-eq #t (endp ()))
+    (assert-eq #f (not 1))
+))
+
+(define test-truthiness (lambda ()
+    (assert-eq (list 1 1 1 1) (list (if 0 1 2) (if 2 1 2) (if "" 1 2) (if (list 0) 1 2)))
+    (assert-eq (list 2 2) (list (if #f 1 2) (if () 1 2)))
+))
+
+(define test-not (lambda ()
+    (assert-eq (list #f #t) (list (not 0) (not ())))
+))
+
+(define test-bool-to-string (lambda ()
+    (assert-eq "#t" (to-string #t))
+))
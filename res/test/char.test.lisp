@@ -0,0 +1,43 @@
+(define test-char-literal (lambda ()
+    (assert-eq "char" (type-of #\a))
+    (assert-eq 97 (char->integer #\a))
+    (assert-eq #\a (integer->char 97))
+    (assert-eq " " (to-string #\space))
+    (assert-eq 10 (char->integer #\newline))
+    (assert-eq #\( (string-ref "(" 0))
+    (assert-eq '(#\a #\b) (list #\a #\b))
+))
+
+(define test-unicode-length (lambda ()
+    (assert-eq 5 (length "héllo"))
+    (assert-eq 6 (byte-length "héllo"))
+    (assert-eq 0 (length ""))
+))
+
+(define test-string-ref (lambda ()
+    (assert-eq #\é (string-ref "héllo" 1))
+    (assert-eq #\o (string-ref "héllo" 4))
+    (assert-eq "error" (type-of (try (string-ref "héllo" 5))))
+    (assert-eq "error" (type-of (try (string-ref "héllo" -1))))
+))
+
+(define test-string-list-conversion (lambda ()
+    (assert-eq '(#\h #\é #\y) (string->list "héy"))
+    (assert-eq "héy" (list->string '(#\h #\é #\y)))
+    (assert-eq "" (list->string ()))
+    (assert-eq "error" (type-of (try (list->string '("a")))))
+))
+
+(define test-char-concat (lambda ()
+    (assert-eq "ab!" (str-concat "a" #\b #\!))
+))
+
+(define test-char-errors (lambda ()
+    (assert-eq "error" (type-of (try (integer->char -1))))
+    (assert-eq "error" (type-of (try (eval "#\\foo"))))
+))
+
+(define test-chars-on-new-line (lambda ()
+    (assert-eq '(#\a #\b) (list
+        #\a #\b))
+))
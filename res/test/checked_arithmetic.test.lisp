@@ -0,0 +1,28 @@
+(define test-division-by-zero (lambda ()
+    (assert-eq "Error: Division by zero" (to-string (try (/ 1 0))))
+    (assert-eq "Error: Division by zero" (to-string (try (mod 1 0))))
+    (assert-eq "Error: Division by zero" (to-string (try (/ 1/2 0))))
+    (assert-eq "float" (type-of (/ 1.0 0)))
+))
+
+(define test-type-errors (lambda ()
+    (assert-eq "error" (type-of (try (+ 1 "a"))))
+    (assert-eq "error" (type-of (try (mod "a" 1))))
+))
+
+(define test-wrapping (lambda ()
+    (assert-eq -9223372036854775808 (+% 9223372036854775807 1))
+    (assert-eq 9223372036854775807 (-% -9223372036854775808 1))
+    (assert-eq -2 (*% 9223372036854775807 2))
+    (assert-eq 6 (+% 1 2 3))
+    (assert-eq "Error: Arithmetic overflow: 9223372036854775808 does not fit into a fixed size integer"
+        (to-string (try (+% 9223372036854775808 1))))
+    (assert-eq "error" (type-of (try (+% 1.0 1))))
+))
+
+(define test-saturating (lambda ()
+    (assert-eq 9223372036854775807 (+| 9223372036854775807 1))
+    (assert-eq -9223372036854775808 (-| -9223372036854775808 1))
+    (assert-eq -9223372036854775808 (*| 9223372036854775807 -2))
+    (assert-eq 5 (-| 10 3 2))
+))
@@ -0,0 +1,33 @@
+(define adder (lambda (n) (lambda (x) (+ x n))))
+
+(define test-closure-captures-param (lambda ()
+    (define add5 (adder 5))
+    (assert-eq 8 (add5 3))
+))
+
+(define test-closures-are-independent (lambda ()
+    (define add1 (adder 1))
+    (define add2 (adder 2))
+    (assert-eq (list 11 12) (list (add1 10) (add2 10)))
+))
+
+(define test-closure-outlives-call (lambda ()
+    (define make-pair-fn (lambda (a b) (lambda (f) (f a b))))
+    (define p (make-pair-fn 3 4))
+    (assert-eq 7 (p +))
+))
+
+(define test-immediate-call (lambda ()
+    (assert-eq 6 ((adder 4) 2))
+))
+
+(define test-builtin-is-captured-as-value (lambda ()
+    (define mk (lambda (f) (lambda (cdr) (f cdr))))
+    (assert-eq (list 2) ((mk cdr) (list 1 2)))
+))
+
+(define test-builtin-survives-shadowing (lambda ()
+    (define f +)
+    (assert-eq 3 (let ((+ 100)) (f 1 2)))
+    (assert-eq "native" (type-of f))
+))
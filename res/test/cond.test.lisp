@@ -0,0 +1,60 @@
+(define classify (lambda (n)
+    (cond
+        ((< n 0) "negative")
+        ((== n 0) "zero")
+        (else "positive")
+    )
+))
+
+(define test-cond (lambda ()
+    (assert-eq (list "negative" "zero" "positive") (list (classify -3) (classify 0) (classify 3)))
+))
+
+(define test-cond-no-match (lambda ()
+    (assert-eq () (cond ((== 1 2) 1)))
+))
+
+(define test-cond-test-only-clause (lambda ()
+    (assert-eq 3 (cond ((== 1 2)) ((+ 1 2))))
+))
+
+(define test-cond-evaluates-body-in-order (lambda ()
+    (define x 0)
+    (assert-eq 2 (cond ((== 1 1) (set! x 1) (+ x 1))))
+))
+
+(define test-cond-tail-position (lambda ()
+    (define loop (lambda (n) (cond ((== n 0) "done") (else (loop (- n 1))))))
+    (assert-eq "done" (loop 10000))
+))
+
+(define day-kind (lambda (day)
+    (case day
+        ((sat sun) "weekend")
+        ((mon tue wed thu fri) "weekday")
+        (else "unknown")
+    )
+))
+
+(define test-case (lambda ()
+    (assert-eq (list "weekend" "weekday" "unknown") (list (day-kind 'sun) (day-kind 'wed) (day-kind 'foo)))
+))
+
+(define test-case-single-key-and-strings (lambda ()
+    (assert-eq "two" (case (+ 1 1) (1 "one") (2 "two")))
+    (assert-eq 1 (case "b" (("a" "b") 1) (else 2)))
+))
+
+(define test-case-no-match (lambda ()
+    (assert-eq () (case 3 ((1 2) "small")))
+))
+
+(define test-when (lambda ()
+    (assert-eq 2 (when (== 1 1) 1 2))
+    (assert-eq () (when (== 1 2) 1 2))
+))
+
+(define test-unless (lambda ()
+    (assert-eq () (unless (== 1 1) 1 2))
+    (assert-eq 2 (unless (== 1 2) 1 2))
+))
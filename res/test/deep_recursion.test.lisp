@@ -0,0 +1,30 @@
+(include "../res/list_util.lisp")
+
+(define test-deep-non-tail-recursion (lambda ()
+    (assert-eq 5000 (length (seq 5000)))
+))
+
+(define test-deep-map (lambda ()
+    (define l (map (lambda (v) (* v 2)) (seq 3000)))
+    (assert-eq 6000 (car l))
+))
+
+(define test-long-list-tail-recursion (lambda ()
+    (assert-eq 200010000 (sum-all 0 (seq 20000)))
+))
+
+(define test-cons-shares-tail (lambda ()
+    (define tail (seq 3))
+    (define a (cons 10 tail))
+    (define b (cons 20 tail))
+    (assert-eq tail (cdr a))
+    (assert-eq (cdr a) (cdr b))
+    (assert-eq '(20 3 2 1) b)
+))
+
+(define test-drop-long-pair-chain (lambda ()
+    (define build (lambda (n p) (if (== n 0) p (build (- n 1) (cons n p)))))
+    (define p (build 100000 0))
+    (assert-eq 1 (car p))
+    (set! p 0)
+))
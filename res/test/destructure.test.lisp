@@ -0,0 +1,35 @@
+(include "../res/list_util.lisp")
+
+(define test-define-pattern (lambda ()
+    (define (k v) '("port" 8080))
+    (assert-eq "port" k)
+    (assert-eq 8080 v)
+))
+
+(define test-nested-pattern (lambda ()
+    (define (a (b c) d) '(1 (2 3) 4))
+    (assert-eq '(1 2 3 4) (list a b c d))
+))
+
+(define test-rest-pattern (lambda ()
+    (define (first &rest others) '(1 2 3))
+    (assert-eq 1 first)
+    (assert-eq '(2 3) others)
+))
+
+(define test-lambda-pattern (lambda ()
+    (define swap (lambda ((a b)) (list b a)))
+    (assert-eq '(2 1) (swap '(1 2)))
+    (assert-eq '(("b" 2) ("a" 1)) (map (lambda ((k v)) (list k v)) (list '("b" 2) '("a" 1))))
+))
+
+(define test-let-pattern (lambda ()
+    (assert-eq 3 (let (((a b) '(1 2))) (+ a b)))
+    (assert-eq 6 (let* (((a b) '(1 2)) ((c) (list (+ a b)))) (+ a b c)))
+))
+
+(define test-pattern-mismatch (lambda ()
+    (assert-eq "error" (type-of (try (define (a b) '(1 2 3)))))
+    (assert-eq "error" (type-of (try (define (a b) 1))))
+    (assert-eq "error" (type-of (try ((lambda ((a b)) a) '(1)))))
+))
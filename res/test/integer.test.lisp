@@ -0,0 +1,91 @@
+(define test-division-signs (lambda ()
+    (assert-eq 3 (quotient 7 2))
+    (assert-eq -3 (quotient -7 2))
+    (assert-eq -3 (quotient 7 -2))
+    (assert-eq 1 (remainder 7 2))
+    (assert-eq -1 (remainder -7 2))
+    (assert-eq 1 (remainder 7 -2))
+    (assert-eq 1 (modulo 7 2))
+    (assert-eq 1 (modulo -7 2))
+    (assert-eq -1 (modulo 7 -2))
+    (assert-eq -1 (modulo -7 -2))
+    (assert-eq 0 (modulo -6 2))
+    (assert-eq 9223372036854775808 (quotient -9223372036854775808 -1))
+    (assert-eq 0 (remainder -9223372036854775808 -1))
+))
+
+(define test-big-division-signs (lambda ()
+    (define big 100000000000000000000)
+    (assert-eq -50000000000000000000 (quotient (- 0 big) 2))
+    (assert-eq -1 (remainder (- 0 (+ big 1)) 2))
+    (assert-eq 1 (modulo (- 0 (+ big 1)) 2))
+    (assert-eq -99999999999999999999 (modulo 1 (- 0 big)))
+))
+
+(define test-division-errors (lambda ()
+    (assert-eq "Error: Division by zero" (to-string (try (quotient 1 0))))
+    (assert-eq "Error: Division by zero" (to-string (try (modulo 100000000000000000000 0))))
+    (assert-eq "error" (type-of (try (quotient 1.5 1))))
+    (assert-eq "error" (type-of (try (remainder 1/2 1))))
+))
+
+(define test-gcd-lcm (lambda ()
+    (assert-eq 3 (gcd 1098 1173))
+    (assert-eq 6 (gcd -12 18))
+    (assert-eq 4 (gcd 8 12 20))
+    (assert-eq 0 (gcd))
+    (assert-eq 429318 (lcm 1098 1173))
+    (assert-eq 12 (lcm -4 6))
+    (assert-eq 0 (lcm 0 5))
+    (assert-eq 1 (lcm))
+    (assert-eq 100000000000000000000 (gcd 100000000000000000000 200000000000000000000))
+))
+
+(define test-bit-operations (lambda ()
+    (assert-eq 8 (bit-and 12 10))
+    (assert-eq 14 (bit-or 12 10))
+    (assert-eq 6 (bit-xor 12 10))
+    (assert-eq 2 (bit-and 15 6 3))
+    (assert-eq -1 (bit-not 0))
+    (assert-eq -13 (bit-not 12))
+    (assert-eq 3 (popcount 11))
+    (assert-eq 0 (popcount 0))
+))
+
+(define test-bit-operations-on-big-integers (lambda ()
+    (assert-eq 0 (bit-and 9223372036854775808 1))
+    (assert-eq 9223372036854775809 (bit-or 9223372036854775808 1))
+    (assert-eq 1 (bit-xor 18446744073709551617 18446744073709551616))
+    (assert-eq 18446744073709551615 (bit-and -18446744073709551617 36893488147419103231))
+    (assert-eq -18446744073709551616 (bit-and -1 -18446744073709551616))
+    (assert-eq 7 (bit-and 15 7 18446744073709551615))
+    (assert-eq -18446744073709551617 (bit-not 18446744073709551616))
+    (assert-eq 18446744073709551616 (bit-not -18446744073709551617))
+    (assert-eq 2 (popcount 18446744073709551617))
+    (assert-eq "error" (type-of (try (popcount -18446744073709551617))))
+    (assert-eq "error" (type-of (try (bit-and 1.5 18446744073709551616))))
+))
+
+(define test-shifts (lambda ()
+    (assert-eq 40 (shift-left 5 3))
+    (assert-eq 18446744073709551616 (shift-left 1 64))
+    (assert-eq -18446744073709551616 (shift-left -1 64))
+    (assert-eq 5 (shift-right 40 3))
+    (assert-eq -2 (shift-right -3 1))
+    (assert-eq -1 (shift-right -1 200))
+    (assert-eq 0 (shift-right 1 200))
+    (assert-eq 1 (shift-right 18446744073709551616 64))
+    (assert-eq -2 (shift-right -18446744073709551617 64))
+    (assert-eq -18446744073709551616 (shift-right -36893488147419103232 1))
+    (assert-eq -1 (shift-right -18446744073709551616 64))
+    (assert-eq -4294967296 (shift-right -79228162514264337593543950336 64))
+    (assert-eq "error" (type-of (try (shift-left 1 -1))))
+    (assert-eq 0 (shift-left 0 9223372036854775807))
+    (assert-eq "Error: Arithmetic overflow: 1 shifted left by 9223372036854775807 bits is too large"
+               (to-string (try (shift-left 1 9223372036854775807))))
+    (assert-eq "error" (type-of (try (shift-left 18446744073709551616 100000000))))
+    (assert-eq "error" (type-of (try (shift-left 1 100000000000000000000))))
+    (assert-eq 0 (shift-right 18446744073709551616 9223372036854775807))
+    (assert-eq -1 (shift-right -18446744073709551616 9223372036854775807))
+    (assert-eq -1 (shift-right -18446744073709551616 65))
+))
@@ -0,0 +1,44 @@
+(define test-keyword-literal (lambda ()
+    (assert-eq :port :port)
+    (assert-eq "keyword" (type-of :port))
+    (assert-eq ":port" (to-string :port))
+))
+
+(define test-key-params (lambda ()
+    (define make-server (lambda (&key (host "localhost") (port 80)) (list host port)))
+    (assert-eq '("localhost" 8080) (make-server :port 8080))
+    (assert-eq '("example.org" 80) (make-server :host "example.org"))
+    (assert-eq '("example.org" 443) (make-server :port 443 :host "example.org"))
+    (assert-eq '("localhost" 80) (make-server))
+))
+
+(define test-key-default-sees-earlier-params (lambda ()
+    (define f (lambda (a &key (b (* a 2))) (list a b)))
+    (assert-eq '(1 2) (f 1))
+    (assert-eq '(1 5) (f 1 :b 5))
+))
+
+(define test-key-after-optional (lambda ()
+    (define f (lambda (a &optional (b 0) &key c) (list a b c)))
+    (assert-eq '(1 0 3) (f 1 :c 3))
+    (assert-eq '(1 2 3) (f 1 2 :c 3))
+    (assert-eq (list 1 2 ()) (f 1 2))
+))
+
+(define test-key-with-rest (lambda ()
+    (define f (lambda (&rest opts &key verbose) (list verbose opts)))
+    (assert-eq '(#t (:verbose #t :other 1)) (f :verbose #t :other 1))
+))
+
+(define test-key-errors (lambda ()
+    (define f (lambda (&key a) a))
+    (assert-eq "error" (type-of (try (f :b 1))))
+    (assert-eq "error" (type-of (try (f :a))))
+    (assert-eq "error" (type-of (try (f 1 2))))
+))
+
+(define test-builtin-keyword-args (lambda ()
+    (assert-eq '("a" "b,c") (str-split "a,b,c" "," :limit 2))
+    (assert-eq '("a" "b" "c") (str-split "a,b,c" ","))
+    (assert-eq "error" (type-of (try (str-split "a,b" "," :max 2))))
+))
@@ -0,0 +1,42 @@
+(define test-let (lambda ()
+    (assert-eq 3 (let ((a 1) (b 2)) (+ a b)))
+))
+
+(define test-let-is-parallel (lambda ()
+    (define x 1)
+    (assert-eq (list 2 1) (let ((x 2) (y x)) (list x y)))
+))
+
+(define test-let-returns-last-form (lambda ()
+    (assert-eq 4 (let ((a 2)) (define b (* a a)) b))
+))
+
+(define test-let-does-not-leak (lambda ()
+    (let ((leaked 1)) (define also-leaked 2) leaked)
+    (assert-eq "error" (type-of (try leaked)))
+    (assert-eq "error" (type-of (try also-leaked)))
+))
+
+(define test-let-star-is-sequential (lambda ()
+    (define x 1)
+    (assert-eq (list 2 3) (let* ((x 2) (y (+ x 1))) (list x y)))
+))
+
+(define test-let-star-later-bindings-invisible (lambda ()
+    (define f (let* ((f (lambda () later)) (later 1)) f))
+    (assert-eq "error" (type-of (try (f))))
+))
+
+(define test-letrec-mutual-recursion (lambda ()
+    (assert-eq 1 (letrec (
+            (is-even (lambda (n) (if (== n 0) 1 (is-odd (- n 1)))))
+            (is-odd (lambda (n) (if (== n 0) 0 (is-even (- n 1)))))
+        )
+        (is-even 10)
+    ))
+))
+
+(define test-let-tail-position (lambda ()
+    (define loop (lambda (n) (let ((m (- n 1))) (if (== m 0) 0 (loop m)))))
+    (assert-eq 0 (loop 10000))
+))
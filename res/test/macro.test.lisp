@@ -0,0 +1,43 @@
+(defmacro my-unless (test alt conseq)
+    (list (quote if) test conseq alt)
+)
+
+(defmacro swap-args (form)
+    (list (car form) (car (cdr (cdr form))) (car (cdr form)))
+)
+
+(defmacro twice (form)
+    (list (quote progn) form form)
+)
+
+(define test-macro-expands (lambda ()
+    (assert-eq 1 (my-unless (== 1 2) 1 2))
+    (assert-eq 2 (my-unless (== 1 1) 1 2))
+))
+
+(define test-macro-args-are-unevaluated (lambda ()
+    (assert-eq 2 (swap-args (- 3 5)))
+))
+
+(define test-macro-only-evaluates-taken-branch (lambda ()
+    (assert-eq 1 (my-unless (== 1 2) 1 (car ())))
+))
+
+(define test-nested-macros (lambda ()
+    (assert-eq -3 (twice (swap-args (- 4 1))))
+))
+
+(define test-macroexpand-1 (lambda ()
+    (define expected (quote (if (== a b) 2 1)))
+    (assert-eq expected (macroexpand-1 (quote (my-unless (== a b) 1 2))))
+))
+
+(define test-macroexpand (lambda ()
+    (define expected (quote (progn (swap-args (- 4 1)) (swap-args (- 4 1)))))
+    (assert-eq expected (macroexpand-1 (quote (twice (swap-args (- 4 1))))))
+    (assert-eq (quote (- 1 4)) (macroexpand (quote (swap-args (- 4 1)))))
+))
+
+(define test-macroexpand-non-macro (lambda ()
+    (assert-eq (quote (+ 1 2)) (macroexpand (quote (+ 1 2))))
+))
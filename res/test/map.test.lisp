@@ -0,0 +1,62 @@
+(define test-map-literal (lambda ()
+    (define m {"port" (+ 8000 80) :host "localhost"})
+    (assert-eq "map" (type-of m))
+    (assert-eq 8080 (hash-get m "port"))
+    (assert-eq "localhost" (hash-get m :host))
+    (assert-eq () (hash-get m "missing"))
+    (assert-eq 0 (hash-get m "missing" 0))
+    (assert-eq 2 (length m))
+))
+
+(define test-map-set-remove (lambda ()
+    (define m {1 "one"})
+    (define m2 (hash-set m 2 "two"))
+    (assert-eq '(1) (hash-keys m))
+    (assert-eq '(1 2) (hash-keys m2))
+    (assert-eq '("one" "two") (hash-values m2))
+    (assert-eq '(2) (hash-keys (hash-remove m2 1)))
+    (assert (hash-has m2 2))
+    (assert (not (hash-has m 2)))
+))
+
+(define test-map-order (lambda ()
+    (assert-eq '(1 3 "a" "b" :k) (hash-keys {"b" 0 :k 0 3 0 "a" 0 1 0}))
+    (assert-eq "{1 a 2 b}" (to-string {2 "b" 1 "a"}))
+))
+
+(define test-map-equality (lambda ()
+    (assert-eq {1 2 3 4} {3 4 1 2})
+    (assert (not (== {1 2} {1 3})))
+    (assert-eq {} (hash-remove {1 2} 1))
+))
+
+(define test-map-errors (lambda ()
+    (assert-eq "error" (type-of (try (hash-map 1))))
+    (assert-eq "error" (type-of (try (hash-set {} (lambda () 1) 1))))
+    (assert-eq "error" (type-of (try (hash-get '(1 2) 1))))
+))
+
+(define test-quoted-map-literal (lambda ()
+    (assert-eq "map" (type-of '{:a 1}))
+    (assert-eq '(a) (hash-values '{:a a}))
+    (assert-eq {:a 1} (hash-set {} :a 1))
+    (define x 2)
+    (assert-eq {:x 2} `{:x ,x})
+    (assert-eq "error" (type-of (try (eval "{1 2 3}"))))
+))
+
+(define test-map-set-in-place (lambda ()
+    (define m {})
+    (define fill (lambda (n)
+        (when (> n 0)
+            (hash-set! m n (* n n))
+            (fill (- n 1)))))
+    (fill 100)
+    (assert-eq 100 (length m))
+    (assert-eq 49 (hash-get m 7))
+    (hash-remove! m 7)
+    (assert (not (hash-has m 7)))
+    (assert-eq "error" (type-of (try (hash-set! undefined-map 1 2))))
+    (assert-eq "error" (type-of (try (hash-set! m (lambda () 1) 2))))
+    (assert-eq 99 (length m))
+))
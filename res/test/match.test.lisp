@@ -0,0 +1,52 @@
+(define describe (lambda (v)
+    (match v
+        (0 "zero")
+        ("" "empty string")
+        ((? integer n) :when (< n 0) "negative")
+        ((? integer) "integer")
+        ((? string s) (str-concat "string " s))
+        ('quit "quit")
+        (() "empty list")
+        ((x) "one element")
+        ((x y) "two elements")
+        ((x &rest _) "many elements")
+        (_ "something else"))))
+
+(define test-match-literals (lambda ()
+    (assert-eq "zero" (describe 0))
+    (assert-eq "empty string" (describe ""))
+    (assert-eq "quit" (describe 'quit))
+))
+
+(define test-match-types (lambda ()
+    (assert-eq "negative" (describe -5))
+    (assert-eq "integer" (describe 5))
+    (assert-eq "string abc" (describe "abc"))
+    (assert-eq "something else" (describe 1.5))
+))
+
+(define test-match-lists (lambda ()
+    (assert-eq "empty list" (describe ()))
+    (assert-eq "one element" (describe '(1)))
+    (assert-eq "two elements" (describe '(1 2)))
+    (assert-eq "many elements" (describe '(1 2 3)))
+))
+
+(define test-match-bindings (lambda ()
+    (assert-eq '(1 (3 4)) (match '(1 (2 3 4)) ((a (_ &rest r)) (list a r))))
+    (assert-eq 3 (match (str-split "key=3" "=") (("key" v) (parse-int v)) (_ 0)))
+))
+
+(define test-match-guard-falls-through (lambda ()
+    (assert-eq "small" (match 5 (n :when (> n 10) "big") (n "small")))
+))
+
+(define test-match-no-clause (lambda ()
+    (assert-eq "error" (type-of (try (match 1 (2 "two")))))
+))
+
+(define test-destructure-with-literal (lambda ()
+    (define ("x" v) '("x" 1))
+    (assert-eq 1 v)
+    (assert-eq "error" (type-of (try (define ("y" w) '("x" 1)))))
+))
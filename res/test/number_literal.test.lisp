@@ -0,0 +1,41 @@
+(define test-radix-literals (lambda ()
+    (assert-eq 255 0xff)
+    (assert-eq 255 0XFF)
+    (assert-eq -16 -0x10)
+    (assert-eq 8 0o10)
+    (assert-eq 10 0b1010)
+    (assert-eq 18446744073709551616 0x1_0000_0000_0000_0000)
+))
+
+(define test-separators (lambda ()
+    (assert-eq 1000000 1_000_000)
+    (assert-eq 1000.5 1_000.5)
+    (assert-eq 3855 0b1111_0000_1111)
+    (assert-eq "symbolref" (type-of '_1))
+    (assert-eq "symbolref" (type-of '1_))
+    (assert-eq "symbolref" (type-of '1__0))
+))
+
+(define test-exponents (lambda ()
+    (assert-eq 1000.0 1e3)
+    (assert-eq 0.0025 2.5e-3)
+    (assert-eq 150.0 1.5E+2)
+    (assert-eq "float" (type-of 1e3))
+    (assert-eq "symbolref" (type-of '1e))
+))
+
+(define test-special-floats (lambda ()
+    (assert (> +inf.0 1e300))
+    (assert (< -inf.0 -1e300))
+    (assert (nan? +nan.0))
+    (assert-eq "symbolref" (type-of 'inf.0))
+))
+
+(define test-identifiers-are-not-numbers (lambda ()
+    (define inf 1)
+    (define nan 2)
+    (define infinity 3)
+    (assert-eq 6 (+ inf nan infinity))
+    (assert-eq "symbolref" (type-of 'NaN))
+    (assert-eq "symbolref" (type-of '-inf))
+))
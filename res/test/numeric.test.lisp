@@ -0,0 +1,103 @@
+(define test-chained-comparison (lambda ()
+    (assert (< 1 2 3))
+    (assert (not (< 1 3 2)))
+    (assert (< 1 1.5 2))
+    (assert (<= 1 1 2))
+    (assert (not (<= 2 1)))
+    (assert (> 3 2.5 1/2))
+    (assert (>= 3 3.0 2))
+    (assert (< 1))
+    (assert-eq "error" (type-of (try (< 1 "a"))))
+    (assert-eq "error" (type-of (try (<))))
+))
+
+(define test-numeric-equality (lambda ()
+    (assert (= 1 1.0))
+    (assert (= 1/2 0.5 2/4))
+    (assert (not (= 1 2)))
+    (assert (!= 1 2))
+    (assert (not (!= 1 1.0)))
+    (assert (not (equal? 1 1.0)))
+    (assert (equal? '(1 2) '(1 2)))
+    (assert (= 9223372036854775808 9223372036854775808.0))
+))
+
+(define test-exact-float-comparison (lambda ()
+    (assert (not (= 99999999999999999999 99999999999999999999.0)))
+    (assert (> 18446744073709551617 18446744073709551616.0))
+    (assert (< 18446744073709551616.0 18446744073709551617))
+    (assert (not (= 9007199254740993 9007199254740992.0)))
+    (assert (not (= 1/3 0.3333333333333333)))
+    (assert (< (shift-left 1 2000) +inf.0))
+    (assert (> (- (shift-left 1 2000)) -inf.0))
+    (assert (> (shift-left 1 2000) 1e308))
+))
+
+(define test-nan (lambda ()
+    (define not-a-number (- (exp 1000) (exp 1000)))
+    (assert (nan? not-a-number))
+    (assert (not (nan? 1)))
+    (assert (not (= not-a-number not-a-number)))
+    (assert (!= not-a-number not-a-number))
+    (assert (not (< not-a-number 1)))
+    (assert (nan? (max 1 not-a-number 2)))
+))
+
+(define test-min-max-abs (lambda ()
+    (assert-eq 1 (min 3 1 2))
+    (assert-eq 3 (max 3 1 2))
+    (assert-eq 1/3 (min 1/2 1/3))
+    (assert-eq 2.5 (max 1 2.5 2))
+    (assert-eq 5 (abs -5))
+    (assert-eq 1/2 (abs -1/2))
+    (assert-eq 1.5 (abs -1.5))
+    (assert-eq 9223372036854775808 (abs -9223372036854775808))
+))
+
+(define test-rounding (lambda ()
+    (assert-eq 2 (floor 7/3))
+    (assert-eq -3 (floor -7/3))
+    (assert-eq 3 (ceil 7/3))
+    (assert-eq 3 (round 5/2))
+    (assert-eq -3 (round -5/2))
+    (assert-eq -2 (truncate -7/3))
+    (assert-eq 2.0 (floor 2.7))
+    (assert-eq -2.0 (truncate -2.7))
+    (assert-eq 4 (round 4))
+))
+
+(define test-powers-and-logs (lambda ()
+    (assert-eq 1024 (expt 2 10))
+    (assert-eq 1/8 (expt 2 -3))
+    (assert-eq 4/9 (expt 2/3 2))
+    (assert-eq 1267650600228229401496703205376 (expt 2 100))
+    (assert-eq 1.0 (expt 2 0.0))
+    (assert-eq 1 (expt 0 0))
+    (assert-eq "error" (type-of (try (expt 0 -1))))
+    (assert-eq "Error: Arithmetic overflow: 2 to the power of 100000000 is too large" (to-string (try (expt 2 100000000))))
+    (assert-eq "error" (type-of (try (expt 3/2 -100000000))))
+    (assert-eq 1 (expt -1 100000000))
+    (assert-eq 0 (expt 0 100000000))
+    (assert-eq 3.0 (sqrt 9))
+    (assert-eq 1.0 (exp 0))
+    (assert-eq 0.0 (log 1))
+    (assert-eq 3.0 (log 8 2))
+))
+
+(define test-trig (lambda ()
+    (assert-eq 0.0 (sin 0))
+    (assert-eq 1.0 (cos 0))
+    (assert (< (abs (- (tan (/ pi 4)) 1)) 0.000001))
+    (assert-eq 0.0 (atan 0))
+    (assert (< (abs (- (* 2 (asin 1)) pi)) 0.000001))
+))
+
+(define test-type-predicates (lambda ()
+    (assert (integer? 1))
+    (assert (integer? 9223372036854775808))
+    (assert (not (integer? 1.0)))
+    (assert (not (integer? 1/2)))
+    (assert (float? 1.0))
+    (assert (not (float? 1)))
+    (assert (not (integer? "a")))
+))
@@ -0,0 +1,49 @@
+(define test-cons-pair (lambda ()
+    (define p (cons 1 2))
+    (assert-eq "pair" (type-of p))
+    (assert-eq 1 (car p))
+    (assert-eq 2 (cdr p))
+    (assert-eq p '(1 . 2))
+))
+
+(define test-dotted-reader (lambda ()
+    (assert-eq (cons 1 (cons 2 3)) '(1 2 . 3))
+    (assert-eq '(1 2 3) '(1 . (2 3)))
+    (assert-eq '(1 2 3) '(1 2 . (3)))
+    (assert-eq "list" (type-of '(1 . ())))
+))
+
+(define test-print-improper-list (lambda ()
+    (assert-eq "(1 . 2)" (to-string (cons 1 2)))
+    (assert-eq "(1 2 . 3)" (to-string '(1 2 . 3)))
+    (assert-eq "(a . b)" (to-string '(a . b)))
+))
+
+(define test-pair-predicates (lambda ()
+    (assert (pair? (cons 1 2)))
+    (assert (pair? '(1)))
+    (assert (not (pair? ())))
+    (assert (not (pair? 1)))
+    (assert (list? ()))
+    (assert (list? '(1 2)))
+    (assert (not (list? '(1 . 2))))
+    (assert (not (list? "a")))
+))
+
+(define test-association-list (lambda ()
+    (define alist (list (cons "a" 1) (cons "b" 2)))
+    (assert-eq 2 (cdr (car (cdr alist))))
+))
+
+(define test-quasiquote-dotted (lambda ()
+    (define b 2)
+    (assert-eq '(1 . 2) `(1 . ,b))
+    (assert-eq '(1 2 . 3) `(1 ,b . 3))
+))
+
+(define test-dotted-errors (lambda ()
+    (assert-eq "error" (type-of (try (eval "(. 1)"))))
+    (assert-eq "error" (type-of (try (eval "'(1 . 2 3)"))))
+    (assert-eq "error" (type-of (try (eval "'(1 .)"))))
+    (assert-eq "error" (type-of (try (eval "(1 . 2)"))))
+))
@@ -0,0 +1,44 @@
+(define test-rest (lambda ()
+    (define f (lambda (a b &rest more) (list a b more)))
+    (assert-eq '(1 2 (3 4)) (f 1 2 3 4))
+    (assert-eq '(1 2 ()) (f 1 2))
+))
+
+(define test-rest-only (lambda ()
+    (define my-list (lambda (&rest items) items))
+    (assert-eq '(1 2 3) (my-list 1 2 3))
+))
+
+(define test-optional (lambda ()
+    (define f (lambda (a &optional b (c 10)) (list a b c)))
+    (assert-eq '(1 2 3) (f 1 2 3))
+    (assert-eq (list 1 2 10) (f 1 2))
+    (assert-eq (list 1 () 10) (f 1))
+))
+
+(define test-optional-default-sees-earlier-params (lambda ()
+    (define f (lambda (a &optional (b (* a 2)) (c (+ a b))) (list a b c)))
+    (assert-eq '(1 2 3) (f 1))
+    (assert-eq '(1 5 6) (f 1 5))
+))
+
+(define test-optional-and-rest (lambda ()
+    (define f (lambda (a &optional (b 0) &rest more) (list a b more)))
+    (assert-eq '(1 0 ()) (f 1))
+    (assert-eq '(1 2 (3)) (f 1 2 3))
+))
+
+(define test-arity-errors (lambda ()
+    (define f (lambda (a b &rest more) a))
+    (define g (lambda (a &optional b) a))
+    (assert-eq "Error: Invalid number of arguments. Expected at least 2 but got 1" (to-string (try (f 1))))
+    (assert-eq "Error: Invalid number of arguments. Expected between 1 and 2 but got 3" (to-string (try (g 1 2 3))))
+))
+
+(defmacro my-progn (&rest body)
+    `(let () ,@body)
+)
+
+(define test-macro-rest (lambda ()
+    (assert-eq 3 (my-progn 1 2 3))
+))
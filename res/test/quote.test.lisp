@@ -0,0 +1,47 @@
+(define test-quote-shorthand (lambda ()
+    (assert-eq (quote a) 'a)
+    (assert-eq (quote (1 (2 b) "c")) '(1 (2 b) "c"))
+    (assert-eq (list 'quote 'x) ''x)
+))
+
+(define test-quasiquote-without-holes (lambda ()
+    (assert-eq '(a b c) `(a b c))
+))
+
+(define test-unquote (lambda ()
+    (define x 42)
+    (assert-eq '(a 42 (b 3)) `(a ,x (b ,(+ 1 2))))
+))
+
+(define test-unquote-splicing (lambda ()
+    (define l (list 2 3))
+    (assert-eq '(1 2 3 4) `(1 ,@l 4))
+    (assert-eq '(1 4) `(1 ,@() 4))
+))
+
+(define test-nested-quasiquote (lambda ()
+    (define x 1)
+    (assert-eq '(a (quasiquote (b (unquote (c 1))))) `(a `(b ,(c ,x))))
+))
+
+(define test-unquote-splicing-outside-list (lambda ()
+    (assert-eq "error" (type-of (try `,@(list 1 2))))
+))
+
+(defmacro my-when (test body)
+    `(if ,test ,body ())
+)
+
+(define test-quasiquote-macro (lambda ()
+    (assert-eq 1 (my-when (== 1 1) 1))
+    (assert-eq () (my-when (== 1 2) 1))
+    (assert-eq '(if (== a b) (c) ()) (macroexpand '(my-when (== a b) (c))))
+))
+
+(define test-call-symbol-cycle (lambda ()
+    (define x 'x)
+    (define a 'b)
+    (define b 'a)
+    (assert-eq "error" (type-of (try (x))))
+    (assert-eq "error" (type-of (try (a 1))))
+))
@@ -0,0 +1,52 @@
+(define test-division-is-exact (lambda ()
+    (assert-eq 1/3 (/ 1 3))
+    (assert-eq "ratio" (type-of (/ 1 3)))
+    (assert-eq 2 (/ 6 3))
+    (assert-eq "integer" (type-of (/ 6 3)))
+    (assert-eq -1/2 (/ 2 -4))
+    (assert-eq 1/2 (/ -2 -4))
+    (assert-eq "error" (type-of (try (/ 1/3 0))))
+))
+
+(define test-literals (lambda ()
+    (assert-eq 1/3 2/6)
+    (assert-eq 2 4/2)
+    (assert-eq -1/3 (- 0 1/3))
+    (assert-eq "1/3" (to-string 1/3))
+    (assert-eq "symbolref" (type-of '1/-3))
+    (assert-eq "symbolref" (type-of '1/0))
+))
+
+(define test-arithmetic (lambda ()
+    (assert-eq 5/6 (+ 1/2 1/3))
+    (assert-eq 1/6 (- 1/2 1/3))
+    (assert-eq 1/6 (* 1/2 1/3))
+    (assert-eq 3/2 (/ 1/2 1/3))
+    (assert-eq 1 (+ 1/3 2/3))
+    (assert-eq 7/2 (+ 3 1/2))
+    (assert-eq 1/6 (mod 1/2 1/3))
+    (assert-eq 0.75 (+ 1/4 0.5))
+))
+
+(define test-compare (lambda ()
+    (assert (< 1/3 1/2))
+    (assert (> 1/2 1/3))
+    (assert (< 1/3 1))
+    (assert (> 1/3 0))
+    (assert (< -1/2 -1/3))
+))
+
+(define test-parts (lambda ()
+    (assert-eq 3 (numerator 6/4))
+    (assert-eq 2 (denominator 6/4))
+    (assert-eq 5 (numerator 5))
+    (assert-eq 1 (denominator 5))
+    (assert-eq 0.25 (exact->inexact 1/4))
+    (assert-eq 2.0 (exact->inexact 2))
+    (assert (< (abs (- 10.0 (exact->inexact (/ (expt 10 400) (+ 1 (expt 10 399)))))) 1e-9))
+    (assert-eq "error" (type-of (try (numerator 0.5))))
+))
+
+(define test-map-key (lambda ()
+    (assert-eq "third" (hash-get (hash-set {} 1/3 "third") (/ 1 3)))
+))
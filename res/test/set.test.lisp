@@ -0,0 +1,50 @@
+(define make-counter (lambda ()
+    (let ((count 0))
+        (lambda ()
+            (set! count (+ count 1))
+            count
+        )
+    )
+))
+
+(define test-set-local (lambda ()
+    (define x 1)
+    (set! x 2)
+    (assert-eq 2 x)
+))
+
+(define test-set-enclosing-scope (lambda ()
+    (define x 1)
+    (let ((y 0))
+        (set! x 5)
+    )
+    (assert-eq 5 x)
+))
+
+(define test-set-counter (lambda ()
+    (define counter (make-counter))
+    (counter)
+    (counter)
+    (assert-eq 3 (counter))
+))
+
+(define test-set-counters-are-independent (lambda ()
+    (define c1 (make-counter))
+    (define c2 (make-counter))
+    (c1)
+    (c1)
+    (assert-eq 1 (c2))
+))
+
+(define test-set-shared-between-closures (lambda ()
+    (define total 0)
+    (define add (lambda (n) (set! total (+ total n))))
+    (define get (lambda () total))
+    (add 3)
+    (add 4)
+    (assert-eq 7 (get))
+))
+
+(define test-set-undefined (lambda ()
+    (assert-eq "error" (type-of (try (set! not-defined-anywhere 1))))
+))
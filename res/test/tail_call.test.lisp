@@ -0,0 +1,41 @@
+(define count-up (lambda (n accu)
+    (if (== n 0)
+        accu
+        (count-up (- n 1) (+ accu 1))
+    )
+))
+
+(define count-up-progn (lambda (n accu)
+    (if (== n 0)
+        accu
+        (progn
+            (define next (+ accu 1))
+            (count-up-progn (- n 1) next)
+        )
+    )
+))
+
+(define count-up-begin (lambda (n accu)
+    (begin
+        (if (== n 0)
+            accu
+            (count-up-begin (- n 1) (+ accu 1))
+        )
+    )
+))
+
+(define test-deep-tail-recursion (lambda ()
+    (assert-eq 100000 (count-up 100000 0))
+))
+
+(define test-deep-tail-recursion-progn (lambda ()
+    (assert-eq 100000 (count-up-progn 100000 0))
+))
+
+(define test-deep-tail-recursion-begin (lambda ()
+    (assert-eq 100000 (count-up-begin 100000 0))
+))
+
+(define test-empty-begin (lambda ()
+    (assert-eq () (begin))
+))
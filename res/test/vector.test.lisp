@@ -0,0 +1,66 @@
+(define test-vector-literal (lambda ()
+    (define v [1 (+ 1 1) "three"])
+    (assert-eq "vector" (type-of v))
+    (assert-eq 3 (vector-length v))
+    (assert-eq 3 (length v))
+    (assert-eq 2 (vector-ref v 1))
+    (assert-eq "[1 2 three]" (to-string v))
+    (assert-eq [] (vector))
+))
+
+(define test-vector-set-push (lambda ()
+    (define v [1 2 3])
+    (assert-eq [1 5 3] (vector-set v 1 5))
+    (assert-eq [1 2 3 4] (vector-push v 4))
+    (assert-eq [1 2 3] v)
+))
+
+(define test-subvector (lambda ()
+    (define v [0 1 2 3 4])
+    (assert-eq [1 2] (subvector v 1 3))
+    (assert-eq [3 4] (subvector v 3))
+    (assert-eq [] (subvector v 5))
+))
+
+(define test-vector-list-conversion (lambda ()
+    (assert-eq '(1 2 3) (vector->list [1 2 3]))
+    (assert-eq [1 2 3] (list->vector '(1 2 3)))
+    (assert-eq [] (list->vector ()))
+))
+
+(define test-vector-errors (lambda ()
+    (assert-eq "error" (type-of (try (vector-ref [1 2] 2))))
+    (assert-eq "error" (type-of (try (vector-ref [1 2] -1))))
+    (assert-eq "error" (type-of (try (vector-set [] 0 1))))
+    (assert-eq "error" (type-of (try (subvector [1 2] 2 1))))
+    (assert-eq "error" (type-of (try (vector-ref '(1 2) 0))))
+))
+
+(define test-mismatched-brackets (lambda ()
+    (assert-eq "error" (type-of (try (eval "[1 2)"))))
+    (assert-eq "error" (type-of (try (eval "(1 2]"))))
+))
+
+(define test-quoted-vector-literal (lambda ()
+    (assert-eq "vector" (type-of '[1 2]))
+    (assert-eq 'a (vector-ref '[a b] 0))
+    (define x 2)
+    (assert-eq [1 2] `[1 ,x])
+    (assert-eq [1 2 3] `[1 ,@(list 2 3)])
+))
+
+(define test-vector-set-push-in-place (lambda ()
+    (define v [])
+    (define fill (lambda (n)
+        (when (< (vector-length v) n)
+            (vector-push! v (vector-length v))
+            (fill n))))
+    (fill 1000)
+    (assert-eq 1000 (vector-length v))
+    (assert-eq 999 (vector-ref v 999))
+    (vector-set! v 0 :first)
+    (assert-eq :first (vector-ref v 0))
+    (assert-eq "error" (type-of (try (vector-set! v 1000 0))))
+    (assert-eq "error" (type-of (try (vector-push! undefined-vector 0))))
+    (assert-eq 1000 (vector-length v))
+))